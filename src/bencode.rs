@@ -0,0 +1,263 @@
+/*! Torrent metainfo utilities
+
+This module contains a minimal decoder for the bencoding used by torrent metainfo (`.torrent`)
+files, and helpers for extracting commonly needed fields from raw `.torrent` file contents.
+
+```no_run
+use rtorrent_xmlrpc_bindings as rtorrent;
+use rtorrent::bencode;
+
+let contents = std::fs::read("example.torrent")?;
+println!("{} ({}): {:?}",
+    bencode::name(&contents)?,
+    bencode::infohash_hex(&contents)?,
+    bencode::announce_list(&contents)?);
+# Ok::<(), rtorrent::Error>(())
+```
+!*/
+
+use crate::{sha1, Error, Result};
+use std::collections::BTreeMap;
+
+/// A decoded bencode value, borrowing from the encoded input.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Value<'a> {
+    Int(i64),
+    Bytes(&'a [u8]),
+    List(Vec<Value<'a>>),
+    Dict(BTreeMap<&'a [u8], Value<'a>>),
+}
+
+impl<'a> Value<'a> {
+    fn get(&self, key: &str) -> Option<&Value<'a>> {
+        match self {
+            Value::Dict(d) => d.get(key.as_bytes()),
+            _ => None,
+        }
+    }
+
+    fn string(&self) -> Option<String> {
+        match self {
+            Value::Bytes(b) => Some(String::from_utf8_lossy(b).into_owned()),
+            _ => None,
+        }
+    }
+}
+
+fn invalid<T>(msg: &str, pos: usize) -> Result<T> {
+    Err(Error::InvalidTorrent(format!("{} at offset {}", msg, pos)))
+}
+
+// The maximum nesting depth of lists and dictionaries.  Real metainfo nests only a few levels
+// deep; the limit keeps hostile input from overflowing the stack.
+const MAX_DEPTH: usize = 64;
+
+struct Decoder<'a> {
+    buf: &'a [u8],
+    pos: usize,
+    depth: usize,
+}
+
+impl<'a> Decoder<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0, depth: 0 }
+    }
+
+    fn peek(&self) -> Result<u8> {
+        match self.buf.get(self.pos) {
+            Some(b) => Ok(*b),
+            None => invalid("unexpected end of input", self.pos),
+        }
+    }
+
+    // Consume bytes up to (and including) `term`, returning the bytes before it as an integer.
+    fn int_until(&mut self, term: u8) -> Result<i64> {
+        let start = self.pos;
+        let len = match self.buf[start..].iter().position(|b| *b == term) {
+            Some(len) => len,
+            None => return invalid("unterminated integer", start),
+        };
+        self.pos += len + 1;
+        std::str::from_utf8(&self.buf[start..start + len])
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .map_or_else(|| invalid("malformed integer", start), Ok)
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8]> {
+        let start = self.pos;
+        let len = self.int_until(b':')?;
+        if len < 0 || (self.buf.len() - self.pos) < len as usize {
+            return invalid("bad string length", start);
+        }
+        let res = &self.buf[self.pos..self.pos + len as usize];
+        self.pos += len as usize;
+        Ok(res)
+    }
+
+    // Consume the opening byte of a list or dictionary.
+    fn enter(&mut self) -> Result<()> {
+        if self.depth == MAX_DEPTH {
+            return invalid("nesting too deep", self.pos);
+        }
+        self.depth += 1;
+        self.pos += 1;
+        Ok(())
+    }
+
+    // Consume the closing `e` of a list or dictionary.
+    fn leave(&mut self) {
+        self.depth -= 1;
+        self.pos += 1;
+    }
+
+    fn value(&mut self) -> Result<Value<'a>> {
+        match self.peek()? {
+            b'i' => {
+                self.pos += 1;
+                Ok(Value::Int(self.int_until(b'e')?))
+            }
+            b'l' => {
+                self.enter()?;
+                let mut list = Vec::new();
+                while self.peek()? != b'e' {
+                    list.push(self.value()?);
+                }
+                self.leave();
+                Ok(Value::List(list))
+            }
+            b'd' => {
+                self.enter()?;
+                let mut dict = BTreeMap::new();
+                while self.peek()? != b'e' {
+                    let key = self.bytes()?;
+                    dict.insert(key, self.value()?);
+                }
+                self.leave();
+                Ok(Value::Dict(dict))
+            }
+            b'0'..=b'9' => Ok(Value::Bytes(self.bytes()?)),
+            _ => invalid("unexpected byte", self.pos),
+        }
+    }
+}
+
+/// Decode a complete bencoded value.
+pub(crate) fn decode(buf: &[u8]) -> Result<Value<'_>> {
+    let mut dec = Decoder::new(buf);
+    let res = dec.value()?;
+    if dec.pos != buf.len() {
+        return invalid("trailing data", dec.pos);
+    }
+    Ok(res)
+}
+
+// Locate the raw encoded bytes of the top-level "info" dictionary.
+fn info_slice(torrent: &[u8]) -> Result<&[u8]> {
+    let mut dec = Decoder::new(torrent);
+    if dec.peek()? != b'd' {
+        return invalid("metainfo is not a dictionary", 0);
+    }
+    dec.pos += 1;
+    while dec.peek()? != b'e' {
+        let key = dec.bytes()?;
+        let start = dec.pos;
+        dec.value()?;
+        if key == b"info" {
+            return Ok(&torrent[start..dec.pos]);
+        }
+    }
+    invalid("metainfo has no info dictionary", dec.pos)
+}
+
+/// Compute the infohash (SHA1 of the bencoded "info" dictionary) of the given `.torrent` file
+/// contents, as an uppercase hex string (the same format rtorrent uses).
+pub fn infohash_hex(torrent: &[u8]) -> Result<String> {
+    let digest = sha1::digest(info_slice(torrent)?);
+    Ok(digest.iter().map(|b| format!("{:02X}", b)).collect())
+}
+
+/// Get the name of the torrent (the suggested file or directory name) from the given `.torrent`
+/// file contents.
+pub fn name(torrent: &[u8]) -> Result<String> {
+    decode(torrent)?
+        .get("info")
+        .and_then(|info| info.get("name"))
+        .and_then(Value::string)
+        .map_or_else(|| invalid("metainfo has no name", 0), Ok)
+}
+
+/// Get the tracker announce URLs from the given `.torrent` file contents.
+///
+/// The result is a list of tiers, each of which is a list of URLs (see BEP 12).  If the torrent
+/// has no `announce-list`, its single `announce` URL (if any) is returned as the only tier.
+pub fn announce_list(torrent: &[u8]) -> Result<Vec<Vec<String>>> {
+    let meta = decode(torrent)?;
+    if let Some(Value::List(tiers)) = meta.get("announce-list") {
+        return Ok(tiers.iter()
+            .filter_map(|tier| match tier {
+                Value::List(urls) => Some(urls.iter().filter_map(Value::string).collect()),
+                _ => None,
+            })
+            .collect());
+    }
+    Ok(meta.get("announce")
+        .and_then(Value::string)
+        .map(|url| vec![vec![url]])
+        .unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A single-file torrent; its infohash was computed independently (with Python's hashlib).
+    fn example_torrent() -> Vec<u8> {
+        let mut torrent = b"d8:announce35:http://tracker.example.org/announce\
+                            7:comment7:example\
+                            4:infod6:lengthi1048576e4:name11:example.iso\
+                            12:piece lengthi262144e6:pieces80:".to_vec();
+        torrent.extend(0..80);
+        torrent.extend_from_slice(b"ee");
+        torrent
+    }
+
+    #[test]
+    fn infohash() {
+        assert_eq!(infohash_hex(&example_torrent()).unwrap(),
+                   "F27937E9F0729BE0CF9AEC3BF95C125E6A7BFA01");
+    }
+
+    #[test]
+    fn fields() {
+        let torrent = example_torrent();
+        assert_eq!(name(&torrent).unwrap(), "example.iso");
+        assert_eq!(announce_list(&torrent).unwrap(),
+                   vec![vec!["http://tracker.example.org/announce".to_owned()]]);
+    }
+
+    #[test]
+    fn malformed() {
+        for input in &[&b""[..], b"d", b"i12", b"ixe", b"5:abc", b"-1:", b"li1ee1", b"x"] {
+            assert!(matches!(decode(input), Err(Error::InvalidTorrent(_))), "{:?}", input);
+        }
+        assert!(matches!(infohash_hex(b"d4:name3:fooe"), Err(Error::InvalidTorrent(_))));
+    }
+
+    #[test]
+    fn nesting_limit() {
+        let nested = |depth| {
+            let mut buf = vec![b'l'; depth];
+            buf.extend(vec![b'e'; depth]);
+            buf
+        };
+        assert!(decode(&nested(MAX_DEPTH)).is_ok());
+        assert!(matches!(decode(&nested(MAX_DEPTH + 1)), Err(Error::InvalidTorrent(_))));
+        // Deep enough to overflow the stack without the limit.
+        let deep = vec![b'l'; 1_000_000];
+        assert!(matches!(decode(&deep), Err(Error::InvalidTorrent(_))));
+        let mut torrent = b"d4:info".to_vec();
+        torrent.extend(&deep);
+        assert!(matches!(infohash_hex(&torrent), Err(Error::InvalidTorrent(_))));
+    }
+}
@@ -0,0 +1,160 @@
+/*! Cached reads
+
+This module defines the [`CachedServer`] type and support code.
+
+[`CachedServer`]: crate::CachedServer
+!*/
+
+use crate::{Download, DownloadSummary, Result, Server, Stats};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+// A single memoized result.  The lock is held while the value is fetched, so concurrent callers
+// wait for (and share) the result of one request rather than each issuing their own.
+#[derive(Debug)]
+struct Slot<T> {
+    entry: Mutex<Option<(Instant, Arc<T>)>>,
+}
+
+impl<T> Slot<T> {
+    fn new() -> Self {
+        Self { entry: Mutex::new(None) }
+    }
+
+    fn get<F: FnOnce() -> Result<T>>(&self, ttl: Duration, fetch: F) -> Result<Arc<T>> {
+        let mut entry = self.entry.lock().unwrap();
+        if let Some((fetched, val)) = &*entry {
+            if fetched.elapsed() < ttl {
+                return Ok(val.clone());
+            }
+        }
+        let val = Arc::new(fetch()?);
+        *entry = Some((Instant::now(), val.clone()));
+        Ok(val)
+    }
+
+    fn clear(&self) {
+        *self.entry.lock().unwrap() = None;
+    }
+}
+
+// Memoized results keyed by argument (e.g., view name).
+#[derive(Debug)]
+struct KeyedSlots<T> {
+    slots: Mutex<BTreeMap<String, Arc<Slot<T>>>>,
+}
+
+impl<T> KeyedSlots<T> {
+    fn new() -> Self {
+        Self { slots: Mutex::new(BTreeMap::new()) }
+    }
+
+    fn get<F: FnOnce() -> Result<T>>(&self, key: &str, ttl: Duration, fetch: F)
+        -> Result<Arc<T>> {
+        let slot = self.slots.lock().unwrap()
+            .entry(key.to_owned())
+            .or_insert_with(|| Arc::new(Slot::new()))
+            .clone();
+        slot.get(ttl, fetch)
+    }
+
+    fn clear(&self) {
+        self.slots.lock().unwrap().clear();
+    }
+}
+
+#[derive(Debug)]
+struct CachedServerInner {
+    server: Server,
+    ttl: Duration,
+    download_list: Slot<Vec<Download>>,
+    download_summaries: KeyedSlots<Vec<(Download, DownloadSummary)>>,
+    stats: Slot<Stats>,
+}
+
+/// `CachedServer` memoizes expensive reads from a [`Server`] for a fixed time-to-live
+///
+/// Applications that display the same data in several places (e.g., multiple widgets of a UI) can
+/// share a `CachedServer` (it is cheap to clone) so that only one request is made per TTL.
+/// Concurrent requests for the same data are coalesced: one caller queries the server, and the
+/// others wait for and share its result.  Errors are not cached.
+///
+/// ```no_run
+/// use rtorrent_xmlrpc_bindings as rtorrent;
+/// use std::time::Duration;
+///
+/// let my_handle = rtorrent::Server::new("http://1.2.3.4/RPC2");
+/// let cached = rtorrent::CachedServer::new(&my_handle, Duration::from_secs(2));
+///
+/// // Only the first of these calls queries rtorrent (within two seconds).
+/// println!("{} downloads", cached.download_summaries("main")?.len());
+/// for (download, summary) in cached.download_summaries("main")?.iter() {
+///     println!("{}: {}", download, summary.name);
+/// }
+/// # Ok::<(), rtorrent::Error>(())
+/// ```
+///
+/// [`Server`]: crate::Server
+#[derive(Clone, Debug)]
+pub struct CachedServer {
+    inner: Arc<CachedServerInner>,
+}
+
+impl CachedServer {
+    /// Wrap `server`, memoizing results for `ttl`.
+    pub fn new(server: &Server, ttl: Duration) -> Self {
+        Self {
+            inner: Arc::new(CachedServerInner {
+                server: server.clone(),
+                ttl,
+                download_list: Slot::new(),
+                download_summaries: KeyedSlots::new(),
+                stats: Slot::new(),
+            })
+        }
+    }
+
+    /// Get the underlying (uncached) `Server`.
+    pub fn server(&self) -> &Server {
+        &self.inner.server
+    }
+
+    /// Get the time-to-live of cached results.
+    pub fn ttl(&self) -> Duration {
+        self.inner.ttl
+    }
+
+    /// Get a list of all downloads loaded in this instance (see [`Server::download_list`]).
+    ///
+    /// [`Server::download_list`]: crate::Server::download_list
+    pub fn download_list(&self) -> Result<Arc<Vec<Download>>> {
+        let server = &self.inner.server;
+        self.inner.download_list.get(self.inner.ttl, || server.download_list())
+    }
+
+    /// Get a summary of every download in `view` (see [`Server::download_summaries`]).
+    ///
+    /// [`Server::download_summaries`]: crate::Server::download_summaries
+    pub fn download_summaries(&self, view: &str) -> Result<Arc<Vec<(Download, DownloadSummary)>>> {
+        let server = &self.inner.server;
+        self.inner.download_summaries.get(view, self.inner.ttl,
+                                          || server.download_summaries(view))
+    }
+
+    /// Get global statistics for this instance (see [`Server::stats`]).
+    ///
+    /// [`Server::stats`]: crate::Server::stats
+    pub fn stats(&self) -> Result<Arc<Stats>> {
+        let server = &self.inner.server;
+        self.inner.stats.get(self.inner.ttl, || server.stats())
+    }
+
+    /// Forget all cached results, e.g. after modifying downloads, so that the next read of each
+    /// queries the server.
+    pub fn invalidate(&self) {
+        self.inner.download_list.clear();
+        self.inner.download_summaries.clear();
+        self.inner.stats.clear();
+    }
+}
@@ -6,10 +6,17 @@ This module defines the [`Download`] type and support code.
 !*/
 
 use crate::macros::*;
-use crate::{value_conversion, File, Peer, Result, Server, Tracker};
-use crate::multicall::p;
+use crate::{call_method, peer, tracker, value_conversion, DownloadBackup, Error, File,
+            FilePriority, FileProgress, FileSummary, FileTree, Peer, PeerSummary, Result, Server,
+            SwarmOverview, ToValue, Tracker, TrackerSummary};
+use crate::multicall::{d, f, p, system::SystemMultiCall, t};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::path::{Component, Path, PathBuf};
 use std::sync::Arc;
-use xmlrpc::{Request, Value};
+use std::time::{Duration, Instant};
+use xmlrpc::Value;
 
 macro_rules! d_getter {
     ($(#[$meta:meta])* $method: ident, $result: ty) => {
@@ -17,6 +24,12 @@ macro_rules! d_getter {
     }
 }
 
+macro_rules! d_getter_named {
+    ($(#[$meta:meta])* $method: ident, $result: ty, $apimethod: literal) => {
+        prim_getter_named!($(#[$meta])* "d.", $method, $result, $apimethod);
+    }
+}
+
 macro_rules! d_str_getter {
     ($(#[$meta:meta])* $method: ident) => {
         d_getter!($(#[$meta])* $method, String);
@@ -35,6 +48,12 @@ macro_rules! d_bool_getter {
     }
 }
 
+macro_rules! d_action {
+    ($(#[$meta:meta])* $method: ident) => {
+        d_getter!($(#[$meta])* $method, ());
+    }
+}
+
 macro_rules! d_f1000_getter {
     ($(#[$meta:meta])* $method: ident) => {
         d_getter!($(#[$meta])* $method, f64);
@@ -65,6 +84,202 @@ macro_rules! d_int_setter {
     }
 }
 
+/// The priority of a [`Download`]
+///
+/// Downloads with higher priority are allotted a larger share of bandwidth.  `Off` downloads do
+/// not transfer data.
+///
+/// [`Download`]: crate::Download
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Priority {
+    Off,
+    Low,
+    Normal,
+    High,
+}
+
+impl TryFromValue for Priority {
+    fn try_from_value(val: &Value) -> Result<Self> {
+        match i64::try_from_value(val)? {
+            0 => Ok(Priority::Off),
+            1 => Ok(Priority::Low),
+            2 => Ok(Priority::Normal),
+            3 => Ok(Priority::High),
+            _ => Err(Error::UnexpectedStructure(
+                format!("Got {:?}, expected download priority (0-3)", val)
+            )),
+        }
+    }
+}
+
+impl From<Priority> for Value {
+    fn from(prio: Priority) -> Self {
+        Value::Int64(prio as i64)
+    }
+}
+
+impl ToValue for Priority {
+    fn to_value(&self) -> Value {
+        Value::from(*self)
+    }
+}
+
+/// The overall state of a [`Download`], as returned by [`Download::status`]
+///
+/// [`Download`]: crate::Download
+/// [`Download::status`]: crate::Download::status
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DownloadStatus {
+    /// Started and incomplete.
+    Downloading,
+    /// Started and complete.
+    Seeding,
+    /// Started, but paused.
+    Paused,
+    /// Stopped (or closed).
+    Stopped,
+    /// Being hash checked.
+    Hashing,
+    /// rtorrent or the tracker reported an error, with the given message.
+    Errored(String),
+}
+
+// The getters `DownloadStatus::from_values` expects, in order.
+pub(crate) const STATUS_METHODS: &[&str] = &["d.hashing", "d.message", "d.is_open", "d.is_active",
+                                             "d.state", "d.complete"];
+
+impl DownloadStatus {
+    pub(crate) fn from_values(vals: &[Value]) -> Result<Self> {
+        let hashing = bool::try_from_value(&vals[0])?;
+        let message = String::try_from_value(&vals[1])?;
+        let is_open = bool::try_from_value(&vals[2])?;
+        let is_active = bool::try_from_value(&vals[3])?;
+        let state = bool::try_from_value(&vals[4])?;
+        let complete = bool::try_from_value(&vals[5])?;
+
+        Ok(if hashing {
+            DownloadStatus::Hashing
+        } else if !message.is_empty() {
+            DownloadStatus::Errored(message)
+        } else if !is_open || !state {
+            DownloadStatus::Stopped
+        } else if !is_active {
+            DownloadStatus::Paused
+        } else if complete {
+            DownloadStatus::Seeding
+        } else {
+            DownloadStatus::Downloading
+        })
+    }
+}
+
+/// A summary of commonly displayed information about a [`Download`], as returned by
+/// [`Download::summary`]
+///
+/// [`Download`]: crate::Download
+/// [`Download::summary`]: crate::Download::summary
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug)]
+pub struct DownloadSummary {
+    /// The name of the torrent.
+    pub name: String,
+    /// The size, in bytes, of the torrent contents.
+    pub size_bytes: i64,
+    /// The number of completed bytes.
+    pub completed_bytes: i64,
+    /// The download rate (bytes/s).
+    pub down_rate: i64,
+    /// The upload rate (bytes/s).
+    pub up_rate: i64,
+    /// The upload/download ratio.
+    pub ratio: f64,
+    /// The overall state of the download.
+    pub status: DownloadStatus,
+    /// The ruTorrent-style label (see [`Download::label`]).
+    pub label: String,
+    /// Unstructured error messages, either generated by rtorrent, or forwarded from the tracker.
+    pub message: String,
+    /// The directory the download's data is stored in.
+    pub directory: String,
+    /// The 'creation date' field of the torrent (timestamp).
+    pub creation_date: i64,
+    /// The timestamp the torrent was loaded (or rtorrent was restarted).
+    pub load_date: i64,
+    /// The timestamp the download was last started.
+    pub timestamp_started: i64,
+    /// The timestamp the download finished (zero if it has not).
+    pub timestamp_finished: i64,
+}
+
+// The getters `DownloadSummary::from_values` expects after `STATUS_METHODS`, in order.
+const SUMMARY_METHODS: &[&str] = &["d.name", "d.size_bytes", "d.completed_bytes", "d.down.rate",
+                                   "d.up.rate", "d.ratio", "d.custom1", "d.directory",
+                                   "d.creation_date", "d.load_date", "d.timestamp.started",
+                                   "d.timestamp.finished"];
+
+impl DownloadSummary {
+    // All of the getters `from_values` expects, in order.
+    pub(crate) fn methods() -> Vec<&'static str> {
+        STATUS_METHODS.iter().chain(SUMMARY_METHODS).copied().collect()
+    }
+
+    // Summarize each of the downloads `builder` queries, in a single round trip.
+    pub(crate) fn fetch(server: &Server, builder: d::MultiBuilder)
+        -> Result<Vec<(Download, Self)>> {
+        let mut builder = builder.dynamic().call("d.hash");
+        for method in Self::methods() {
+            builder.push(method);
+        }
+        builder.invoke()?
+            .iter()
+            .map(|row| {
+                Ok((Download::from_value(server, &row[0])?, Self::from_values(&row[1..])?))
+            })
+            .collect()
+    }
+
+    pub(crate) fn from_values(vals: &[Value]) -> Result<Self> {
+        let status = DownloadStatus::from_values(vals)?;
+        let message = String::try_from_value(&vals[1])?;
+        let vals = &vals[STATUS_METHODS.len()..];
+        Ok(Self {
+            name: String::try_from_value(&vals[0])?,
+            size_bytes: i64::try_from_value(&vals[1])?,
+            completed_bytes: i64::try_from_value(&vals[2])?,
+            down_rate: i64::try_from_value(&vals[3])?,
+            up_rate: i64::try_from_value(&vals[4])?,
+            ratio: f64::try_from_value(&vals[5])?,
+            status,
+            label: label_decode(&String::try_from_value(&vals[6])?),
+            message,
+            directory: String::try_from_value(&vals[7])?,
+            creation_date: i64::try_from_value(&vals[8])?,
+            load_date: i64::try_from_value(&vals[9])?,
+            timestamp_started: i64::try_from_value(&vals[10])?,
+            timestamp_finished: i64::try_from_value(&vals[11])?,
+        })
+    }
+}
+
+/// Swarm size for a [`Download`], as reported by tracker scrapes
+///
+/// Returned by [`Download::swarm_info`].
+///
+/// [`Download`]: crate::Download
+/// [`Download::swarm_info`]: crate::Download::swarm_info
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SwarmInfo {
+    /// The number of seeders (complete peers).
+    pub seeders: i64,
+    /// The number of leechers (incomplete peers).
+    pub leechers: i64,
+    /// The number of times the torrent has been completely downloaded.
+    pub downloaded: i64,
+}
+
 #[derive(Debug)]
 pub(crate) struct DownloadInner {
     sha1_hex: String,
@@ -175,6 +390,40 @@ impl Download {
         Self { inner: Arc::new(DownloadInner { server, sha1_hex: hash.to_owned() }) }
     }
 
+    /// Construct a Download representing the given infohash on the specified server, validating
+    /// that `hash` is a well-formed infohash and that the download exists on the server.  The
+    /// infohash is normalized to uppercase, as rtorrent reports it.
+    ///
+    /// Returns [`Error::InvalidInfohash`] or [`Error::DownloadNotFound`], respectively, if not.
+    ///
+    /// [`Error::InvalidInfohash`]: crate::Error::InvalidInfohash
+    /// [`Error::DownloadNotFound`]: crate::Error::DownloadNotFound
+    pub fn try_from_hash(server: &Server, hash: &str) -> Result<Self> {
+        if hash.len() != 40 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(Error::InvalidInfohash(hash.to_owned()));
+        }
+        let dl = Self::from_hash(server, &hash.to_ascii_uppercase());
+        if !dl.exists()? {
+            return Err(Error::DownloadNotFound(dl.sha1_hex().to_owned()));
+        }
+        Ok(dl)
+    }
+
+    /// Check whether this download is (still) loaded on the server.
+    pub fn exists(&self) -> Result<bool> {
+        match call_method::<String>(self.endpoint(), "d.hash", vec![Value::from(self)]) {
+            Ok(_) => Ok(true),
+            // rtorrent faults on unknown infohashes; anything else is a real error.
+            Err(e) if e.is_not_found() => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn server(&self) -> &Server {
+        &self.inner.server
+    }
+
     #[inline]
     pub(crate) fn endpoint(&self) -> &str {
         self.inner.server.endpoint()
@@ -185,7 +434,38 @@ impl Download {
         &self.inner.sha1_hex
     }
 
+    /// Describe this download for logs and messages, as "name (infohash prefix)".  The name is
+    /// fetched from the server.
+    pub fn describe(&self) -> Result<String> {
+        let hash = self.sha1_hex();
+        Ok(format!("{} ({})", self.name()?, hash.get(..8).unwrap_or(hash)))
+    }
+
+    // Invoke several `d.*` getters against this download in a single round trip.
+    fn batch(&self, methods: &[&str]) -> Result<Vec<Value>> {
+        let mut mc = SystemMultiCall::new();
+        for method in methods {
+            mc.push(method, vec![Value::from(self)]);
+        }
+        mc.invoke(&self.inner.server)
+    }
+
+    /// Get the overall state of the download, in a single round trip.
+    pub fn status(&self) -> Result<DownloadStatus> {
+        DownloadStatus::from_values(&self.batch(STATUS_METHODS)?)
+    }
+
+    /// Get a summary of commonly displayed information about the download, in a single round
+    /// trip.
+    pub fn summary(&self) -> Result<DownloadSummary> {
+        DownloadSummary::from_values(&self.batch(&DownloadSummary::methods())?)
+    }
+
     /// Get a list of active peers associated with this download.
+    ///
+    /// Each accessor of the returned peers is a separate round trip; see [`peers_snapshot`].
+    ///
+    /// [`peers_snapshot`]: crate::Download::peers_snapshot
     pub fn peers(&self) -> Result<Vec<Peer>> {
         p::MultiBuilder::new(&self.inner.server, self.sha1_hex())
             .call(p::ID)
@@ -196,17 +476,337 @@ impl Download {
     }
 
     /// Get a list of files associated with this download.
+    ///
+    /// Each accessor of the returned files is a separate round trip; use [`file_summaries`] to
+    /// fetch commonly displayed information about every file at once.
+    ///
+    /// [`file_summaries`]: crate::Download::file_summaries
     pub fn files(&self) -> Result<Vec<File>> {
         let num = self.size_files()?;
         Ok((0..num).map(|i| File::new(self.clone(), i)).collect())
     }
 
+    /// Get the path, size, priority, and completion of each file associated with this download,
+    /// in a single round trip.
+    pub fn file_summaries(&self) -> Result<Vec<FileSummary>> {
+        Ok(f::MultiBuilder::new(&self.inner.server, self.sha1_hex(), None)
+           .call(f::PATH)
+           .call(f::SIZE_BYTES)
+           .call(f::PRIORITY_LEVEL)
+           .call(f::COMPLETED_CHUNKS)
+           .call(f::SIZE_CHUNKS)
+           .invoke()?
+           .into_iter()
+           .enumerate()
+           .map(|(index, (path, size_bytes, priority, completed_chunks, size_chunks))| {
+               FileSummary {
+                   file: File::new(self.clone(), index as i64),
+                   path,
+                   size_bytes,
+                   priority,
+                   completed_chunks,
+                   size_chunks,
+               }
+           })
+           .collect())
+    }
+
+    /// Get the completion of each file associated with this download.
+    ///
+    /// rtorrent tracks completion per chunk (piece), not per file.  This method combines the
+    /// download's bitfield with each file's location in the torrent data to compute, for each
+    /// file, the number of its bytes contained in completed chunks.  It uses two round trips.
+    pub fn file_progress(&self) -> Result<Vec<FileProgress>> {
+        let vals = self.batch(&["d.bitfield", "d.chunk_size", "d.complete"])?;
+        let bitfield = value_conversion::string(&vals[0])?;
+        let chunk_size = i64::try_from_value(&vals[1])?;
+        let complete = bool::try_from_value(&vals[2])?;
+
+        // The bitfield is hex-encoded, with chunk 0 in the most significant bit of the first
+        // byte.  rtorrent may report an empty bitfield for closed downloads.
+        let nibbles = bitfield.chars()
+            .map(|c| c.to_digit(16).ok_or_else(|| Error::UnexpectedStructure(
+                format!("Got {:?}, expected hex bitfield", bitfield)
+            )))
+            .collect::<Result<Vec<_>>>()?;
+        let chunk_done = |chunk: i64| {
+            if nibbles.is_empty() {
+                return complete;
+            }
+            let nibble = nibbles.get((chunk / 4) as usize).copied().unwrap_or(0);
+            nibble & (0x8 >> (chunk % 4)) != 0
+        };
+
+        f::MultiBuilder::new(&self.inner.server, self.sha1_hex(), None)
+            .call(f::PATH)
+            .call(f::OFFSET)
+            .call(f::SIZE_BYTES)
+            .invoke()?
+            .into_iter()
+            .enumerate()
+            .map(|(index, (path, offset, size_bytes))| {
+                let end = offset + size_bytes;
+                let mut completed_bytes = 0;
+                if chunk_size > 0 && size_bytes > 0 {
+                    for chunk in (offset / chunk_size)..=((end - 1) / chunk_size) {
+                        if chunk_done(chunk) {
+                            let chunk_start = chunk * chunk_size;
+                            completed_bytes += end.min(chunk_start + chunk_size)
+                                - offset.max(chunk_start);
+                        }
+                    }
+                }
+                Ok(FileProgress {
+                    file: File::new(self.clone(), index as i64),
+                    path,
+                    size_bytes,
+                    completed_bytes,
+                })
+            })
+            .collect()
+    }
+
+    /// Get the contents of this download as a nested directory structure, in a single round
+    /// trip.
+    pub fn file_tree(&self) -> Result<FileTree> {
+        FileTree::fetch(self)
+    }
+
+    /// Set the priority of every file in this download matching `glob` (e.g., `"*.nfo"`), and
+    /// apply the new priorities, in a single round trip.  Returns the number of matching files.
+    ///
+    /// ```no_run
+    /// # use rtorrent_xmlrpc_bindings as rtorrent;
+    /// use rtorrent::{Download, FilePriority};
+    ///
+    /// fn skip_samples(dl: &Download) -> rtorrent::Result<()> {
+    ///     dl.set_file_priority_glob("*sample*", FilePriority::Off)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_file_priority_glob(&self, glob: &str, priority: FilePriority) -> Result<usize> {
+        let mut mc = SystemMultiCall::new();
+        mc.push("f.multicall", vec![
+            Value::from(self),
+            Value::from(glob),
+            Value::from(format!("f.priority.set={}", priority as i64)),
+        ]);
+        mc.push("d.update_priorities", vec![Value::from(self)]);
+        let vals = mc.invoke(&self.inner.server)?;
+        Ok(value_conversion::list(&vals[0])?.len())
+    }
+
     /// Get a list of trackers associated with this download.
+    ///
+    /// Each accessor of the returned trackers is a separate round trip; see
+    /// [`trackers_snapshot`].
+    ///
+    /// [`trackers_snapshot`]: crate::Download::trackers_snapshot
     pub fn trackers(&self) -> Result<Vec<Tracker>> {
         let num = self.tracker_size()?;
         Ok((0..num).map(|i| Tracker::new(self.clone(), i)).collect())
     }
 
+    /// Get a summary of each tracker associated with this download, in a single round trip.
+    pub fn tracker_summaries(&self) -> Result<Vec<TrackerSummary>> {
+        let mut builder = t::MultiBuilder::new(&self.inner.server, self.sha1_hex()).dynamic();
+        for method in tracker::SUMMARY_METHODS {
+            builder.push(method);
+        }
+        builder.invoke()?
+            .iter()
+            .enumerate()
+            .map(|(index, row)| TrackerSummary::from_values(index as i64, row))
+            .collect()
+    }
+
+    /// Get a summary of each peer connected for this download, in a single round trip.
+    pub fn peer_summaries(&self) -> Result<Vec<PeerSummary>> {
+        let mut builder = p::MultiBuilder::new(&self.inner.server, self.sha1_hex()).dynamic();
+        for method in peer::SUMMARY_METHODS {
+            builder.push(method);
+        }
+        builder.invoke()?
+            .iter()
+            .map(|row| PeerSummary::from_values(row))
+            .collect()
+    }
+
+    /// Get a handle to each tracker associated with this download, with a summary of it (see
+    /// [`tracker_summaries`]), in a single round trip.
+    ///
+    /// [`tracker_summaries`]: crate::Download::tracker_summaries
+    pub fn trackers_snapshot(&self) -> Result<Vec<(Tracker, TrackerSummary)>> {
+        Ok(self.tracker_summaries()?
+           .into_iter()
+           .map(|summary| (Tracker::new(self.clone(), summary.index), summary))
+           .collect())
+    }
+
+    /// Get a handle to each peer connected for this download, with a summary of it (see
+    /// [`peer_summaries`]), in a single round trip.
+    ///
+    /// [`peer_summaries`]: crate::Download::peer_summaries
+    pub fn peers_snapshot(&self) -> Result<Vec<(Peer, PeerSummary)>> {
+        Ok(self.peer_summaries()?
+           .into_iter()
+           .map(|summary| (Peer::new(self.clone(), &summary.id), summary))
+           .collect())
+    }
+
+    /// Get aggregate statistics over the peers connected for this download, in a single round
+    /// trip.
+    ///
+    /// Use [`SwarmOverview::from_summaries`] instead if the individual [`peer_summaries`] are
+    /// also needed.
+    ///
+    /// [`SwarmOverview::from_summaries`]: crate::SwarmOverview::from_summaries
+    /// [`peer_summaries`]: crate::Download::peer_summaries
+    pub fn swarm_overview(&self) -> Result<SwarmOverview> {
+        Ok(SwarmOverview::from_summaries(&self.peer_summaries()?))
+    }
+
+    /// Get the size of the swarm, as reported by tracker scrapes, in a single round trip.
+    ///
+    /// Trackers usually report overlapping sets of peers, so the largest count reported by any
+    /// one tracker is used rather than the sum.
+    pub fn swarm_info(&self) -> Result<SwarmInfo> {
+        t::MultiBuilder::new(&self.inner.server, self.sha1_hex())
+            .call(t::SCRAPE_COMPLETE)
+            .call(t::SCRAPE_INCOMPLETE)
+            .call(t::SCRAPE_DOWNLOADED)
+            .invoke()
+            .map(|rows| rows.into_iter()
+                .fold(SwarmInfo::default(), |acc, (seeders, leechers, downloaded)| SwarmInfo {
+                    seeders: acc.seeders.max(seeders),
+                    leechers: acc.leechers.max(leechers),
+                    downloaded: acc.downloaded.max(downloaded),
+                }))
+    }
+
+    /// Erase the download and delete its data from the rtorrent host.
+    ///
+    /// The download's data path is captured, the download is stopped, closed, and erased, and
+    /// the data is then deleted with `rm -rf` on the rtorrent host.  The data path must be an
+    /// absolute path at least two levels deep, lie within the download's directory, and be deeper
+    /// than the server's default directory (`directory.default`); otherwise,
+    /// [`Error::UnsafePath`] is returned before anything is erased.
+    ///
+    /// [`Error::UnsafePath`]: crate::Error::UnsafePath
+    pub fn erase_with_data(&self) -> Result<()> {
+        let path = self.erase_returning_data_path()?;
+        let path = path.to_string_lossy();
+        self.inner.server.execute("rm", &["-rf", "--", &path])
+    }
+
+    /// Erase the download, returning the path of its data for the caller to delete.
+    ///
+    /// This is useful when the data is accessible locally (or via some other mechanism).  The
+    /// returned path has passed the same safety checks as [`Download::erase_with_data`].
+    pub fn erase_returning_data_path(&self) -> Result<PathBuf> {
+        let mut mc = SystemMultiCall::new();
+        for method in DATA_PATH_METHODS {
+            mc.push(method, vec![Value::from(self)]);
+        }
+        mc.push("directory.default", vec![Value::from("")]);
+        let vals = mc.invoke(&self.inner.server)?;
+        let default_directory = String::try_from_value(&vals[DATA_PATH_METHODS.len()])?;
+        let path = data_path(&vals[..DATA_PATH_METHODS.len()], &default_directory)?;
+
+        self.remove(false)?;
+        Ok(path)
+    }
+
+    /// Remove the download from rtorrent: stop, close, and then erase it.  The data stored for
+    /// the item is not touched.
+    ///
+    /// If `delete_tied` is true, the file the download is tied to (e.g., a `.torrent` file in a
+    /// watch directory) is deleted first; see [`Download::delete_tied`].
+    pub fn remove(&self, delete_tied: bool) -> Result<()> {
+        if delete_tied {
+            self.delete_tied()?;
+        }
+        self.stop()?;
+        self.close()?;
+        self.erase()?;
+        Ok(())
+    }
+
+    /// Copy this download to the rtorrent instance `target`, preserving its data directory, custom
+    /// fields (including the label), priority, views, and started state.
+    ///
+    /// The download is stopped on this instance and its session state saved first, so that the
+    /// metafile carries up-to-date fast-resume data and only one instance transfers the download.
+    /// The download's data is not copied: it must already be available at the same path on the
+    /// `target` host (e.g., on shared storage, or copied beforehand).
+    ///
+    /// See [`Download::migrate_and_erase`] to also remove the download from this instance.
+    pub fn migrate(&self, target: &Server) -> Result<Download> {
+        let mut backup = DownloadBackup::capture(self)?;
+        if backup.started {
+            self.stop()?;
+        }
+        self.save_full_session()?;
+        backup.metafile = self.metafile()?;
+        backup.restore(target)
+    }
+
+    /// Copy this download to `target` (see [`Download::migrate`]), then erase it from this
+    /// instance once `target` has confirmed the data: the copy is hash checked on `target`, and
+    /// must be complete.
+    ///
+    /// If the hash check does not finish within `timeout`, [`Error::Timeout`] is returned; if the
+    /// copy is not complete, [`Error::Incomplete`] is returned.  In either case the download is
+    /// left (stopped) on this instance.  Downloaded data is never deleted.
+    ///
+    /// [`Error::Timeout`]: crate::Error::Timeout
+    /// [`Error::Incomplete`]: crate::Error::Incomplete
+    pub fn migrate_and_erase(&self, target: &Server, timeout: Duration) -> Result<Download> {
+        const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+        let migrated = self.migrate(target)?;
+        migrated.check_hash()?;
+        let start = Instant::now();
+        while migrated.status()? == DownloadStatus::Hashing {
+            if start.elapsed() >= timeout {
+                return Err(Error::Timeout(
+                    format!("waiting for {} to hash check download {}", target.endpoint(),
+                            self.sha1_hex())
+                ));
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        }
+        if !migrated.complete()? {
+            return Err(Error::Incomplete(
+                format!("download {} on {} after hash check", self.sha1_hex(), target.endpoint())
+            ));
+        }
+        self.remove(false)?;
+        Ok(migrated)
+    }
+
+    d_getter!(
+        /// Get the names of the views this download has been tagged into (via
+        /// [`Download::add_view`] or `d.views.push_back_unique`).
+        views, Vec<String>);
+
+    /// Tag this download into `view` (`d.views.push_back_unique`) and make it visible in that
+    /// view.
+    pub fn add_view(&self, view: &str) -> Result<()> {
+        self.view_op("d.views.push_back_unique", view)?;
+        self.view_op("view.set_visible", view)
+    }
+
+    /// Remove this download's tag for `view` (`d.views.remove`) and hide it from that view.
+    pub fn remove_view(&self, view: &str) -> Result<()> {
+        self.view_op("d.views.remove", view)?;
+        self.view_op("view.set_not_visible", view)
+    }
+
+    fn view_op(&self, api: &str, view: &str) -> Result<()> {
+        call_method(self.endpoint(), api, vec![Value::from(self), Value::from(view)])
+    }
+
     d_str_getter!(base_filename);
     d_str_getter!(base_path);
     d_str_getter!(directory);
@@ -214,6 +814,91 @@ impl Download {
     d_str_setter!(set_directory, directory);
     d_str_setter!(set_directory_base, directory_base);
 
+    d_str_getter!(
+        /// Get the `custom1` user-defined field.  By convention, ruTorrent and many other tools
+        /// use this field as the download's label.
+        custom1);
+    d_str_setter!(
+        /// Set the `custom1` user-defined field.
+        set_custom1, custom1);
+
+    /// Get the download's label, as set by ruTorrent (and compatible tools).
+    ///
+    /// ruTorrent stores labels URL-encoded in the `custom1` field; this method decodes them.
+    pub fn label(&self) -> Result<String> {
+        Ok(label_decode(&self.custom1()?))
+    }
+
+    /// Set the download's label, in the URL-encoded form ruTorrent (and compatible tools) expect.
+    /// See [`Download::label`].
+    pub fn set_label(&self, label: &str) -> Result<()> {
+        self.set_custom1(&label_encode(label))
+    }
+
+    d_str_getter!(
+        /// Get the `custom2` user-defined field.
+        custom2);
+    d_str_setter!(
+        /// Set the `custom2` user-defined field.
+        set_custom2, custom2);
+    d_str_getter!(
+        /// Get the `custom3` user-defined field.
+        custom3);
+    d_str_setter!(
+        /// Set the `custom3` user-defined field.
+        set_custom3, custom3);
+    d_str_getter!(
+        /// Get the `custom4` user-defined field.
+        custom4);
+    d_str_setter!(
+        /// Set the `custom4` user-defined field.
+        set_custom4, custom4);
+    d_str_getter!(
+        /// Get the `custom5` user-defined field.
+        custom5);
+    d_str_setter!(
+        /// Set the `custom5` user-defined field.
+        set_custom5, custom5);
+
+    /// Get the value stored under the user-defined custom field `key`.  Unset keys are the empty
+    /// string.
+    pub fn custom(&self, key: &str) -> Result<String> {
+        call_method(self.endpoint(), "d.custom", vec![Value::from(self), Value::from(key)])
+    }
+
+    /// Store `value` under the user-defined custom field `key`.
+    pub fn set_custom(&self, key: &str, value: &str) -> Result<()> {
+        call_method(self.endpoint(), "d.custom.set",
+                    vec![Value::from(self), Value::from(key), Value::from(value)])
+    }
+
+    d_getter_named!(
+        /// Get the keys of all user-defined custom fields set on this download (not including
+        /// `custom1` through `custom5`).  Requires rtorrent 0.9.7 or later.
+        custom_keys, Vec<String>, "custom.keys");
+
+    d_str_getter!(
+        /// Get the current connection type of the download: `"leech"`, `"seed"`,
+        /// `"initial_seed"`, or `"metadata"`.
+        connection_current);
+    d_str_setter!(
+        /// Set the current connection type of the download.  See
+        /// [`Download::connection_current`].
+        set_connection_current, connection_current);
+    d_str_getter!(
+        /// Get the connection type used while the download is incomplete.
+        connection_leech);
+    d_str_setter!(
+        /// Set the connection type used while the download is incomplete.
+        set_connection_leech, connection_leech);
+    d_str_getter!(
+        /// Get the connection type used once the download is complete (e.g., `"seed"` or
+        /// `"initial_seed"`).
+        connection_seed);
+    d_str_setter!(
+        /// Set the connection type used once the download is complete.
+        set_connection_seed, connection_seed);
+
     d_int_getter!(
         /// The item's chunk size, in bytes (also known as "piece size").
         chunk_size);
@@ -239,31 +924,131 @@ impl Download {
         down_total, "down.total");
 
     d_bool_getter!(is_active);
+    d_bool_getter!(
+        /// Is this a multi-file torrent?  If so, [`Download::base_path`] is a directory;
+        /// otherwise, it is the single data file.
+        is_multi_file);
+    d_bool_getter!(
+        /// Is this torrent marked private?  Private torrents only obtain peers from their
+        /// trackers (not from DHT or peer exchange).
+        is_private);
     d_bool_getter!(is_open);
     d_bool_getter!(is_closed);
 
+    d_action!(
+        /// Opens the download's files.
+        open);
+    d_action!(
+        /// Closes the download's files (stopping it, if necessary).  Downloads must be closed
+        /// before their data is hash checked, moved, or deleted.
+        close);
+    d_action!(
+        /// Closes the download's files, unless the download is active.
+        try_close);
+
     d_bool_getter!(
         /// Starts the download.
         start);
     d_bool_getter!(
         /// Stops the download.
         stop);
+    d_action!(
+        /// Pauses the download.  Unlike [`Download::stop`], the item remains in the "started"
+        /// view and its peer connections are preserved.
+        pause);
+    d_action!(
+        /// Resumes a download paused with [`Download::pause`].
+        resume);
     d_bool_getter!(
         /// Removes download from rtorrent's index of torrents, including associated session files.
         /// The data stored for the item is not touched.
         erase);
 
+    d_action!(
+        /// Apply changes to the priorities of the download's files (see
+        /// [`File::set_priority`]).
+        ///
+        /// [`File::set_priority`]: crate::File::set_priority
+        update_priorities);
+    d_action!(
+        /// Write the download's fast-resume data to its session file.
+        save_resume);
+    d_action!(
+        /// Write the download's complete session state (including fast-resume data) to its
+        /// session file.
+        save_full_session);
+
     d_bool_getter!(
         /// Cause the download to be hash checked.  The download is paused during hashing.
         check_hash);
     d_bool_getter!(
         /// Trigger a tracker announce (notify tracker of local status and potentially get
         /// information about new peers).
+        ///
+        /// The announce goes to every usable tracker; rtorrent cannot announce to a single
+        /// tracker.  Use [`Tracker::disable`] to keep a tracker out of announces.
+        ///
+        /// [`Tracker::disable`]: crate::Tracker::disable
         tracker_announce);
 
+    /// Add a tracker with announce URL `url` to this download, in BEP 12 tier `group`.
+    pub fn add_tracker(&self, group: i64, url: &str) -> Result<()> {
+        call_method(self.endpoint(), "d.tracker.insert",
+                    vec![Value::from(self), Value::from(group), Value::from(url)])
+    }
+
+    /// Request a scrape (swarm statistics) from this download's trackers, after `delay` seconds.
+    ///
+    /// The scrape goes to every usable tracker that supports it; rtorrent cannot scrape a single
+    /// tracker.  Use [`Tracker::disable`] to keep a tracker out of scrapes.
+    ///
+    /// [`Tracker::disable`]: crate::Tracker::disable
+    pub fn tracker_send_scrape(&self, delay: i64) -> Result<()> {
+        call_method(self.endpoint(), "d.tracker.send_scrape",
+                    vec![Value::from(self), Value::from(delay)])
+    }
+
     d_str_getter!(
         /// The metafile from which this download was created.
         loaded_file);
+    d_str_getter!(
+        /// The path of this download's copy of its metafile in rtorrent's session directory
+        /// (empty if rtorrent is not configured with a session directory).
+        session_file);
+
+    /// Retrieve the contents of this download's metafile (`.torrent` file) from the rtorrent
+    /// host.
+    ///
+    /// The session directory copy of the metafile is preferred; if there is none, the file the
+    /// download was loaded from is used.  The file is read by running `base64` on the rtorrent
+    /// host.  Note that session copies include rtorrent-specific resume data alongside the
+    /// original metainfo; the infohash is unaffected.
+    pub fn metafile(&self) -> Result<Vec<u8>> {
+        let path = self.metafile_path()?;
+        let encoded = self.inner.server.execute_capture("base64", &["-w0", "--", &path])?;
+        base64_decode(&encoded)
+    }
+
+    /// Read the contents of this download's metafile (`.torrent` file) from the local
+    /// filesystem.  This is only useful if the rtorrent host's filesystem is available locally at
+    /// the same paths.  See [`Download::metafile`].
+    pub fn metafile_local(&self) -> Result<Vec<u8>> {
+        Ok(std::fs::read(self.metafile_path()?)?)
+    }
+
+    fn metafile_path(&self) -> Result<String> {
+        let session = self.session_file()?;
+        if !session.is_empty() {
+            return Ok(session);
+        }
+        let loaded = self.loaded_file()?;
+        if loaded.is_empty() {
+            return Err(Error::UnexpectedStructure(
+                format!("download {} has no session or loaded file", self.sha1_hex())
+            ));
+        }
+        Ok(loaded)
+    }
 
     d_str_getter!(
         /// Unstructured error messages, either generated by rtorrent, or forwarded from the
@@ -280,10 +1065,33 @@ impl Download {
     d_f1000_getter!(
         /// Get the upload/download ratio for this download.
         ratio);
+    d_getter!(
+        /// Get the priority of the download.
+        priority, Priority);
+    d_str_getter!(
+        /// Get the priority of the download as a human-readable string (e.g., `"normal"`).
+        priority_str);
+    prim_setter!(
+        /// Control the priority of the download.
+        "d.", priority_set, priority, Priority);
+    d_int_getter!(
+        /// Get the minimum number of peers rtorrent tries to stay connected to for this download.
+        peers_min);
+    d_int_setter!(
+        /// Set the minimum number of peers for this download.  See [`Download::peers_min`].
+        set_peers_min, peers_min);
+    d_int_getter!(
+        /// Get the maximum number of peers this download may connect to.
+        peers_max);
+    d_int_setter!(
+        /// Set the maximum number of peers for this download.  See [`Download::peers_max`].
+        set_peers_max, peers_max);
+    d_int_getter!(
+        /// Get the maximum number of peers exchanged in a single peer exchange (PEX) message.
+        max_size_pex);
     d_int_setter!(
-        /// Control the priority of the download.  Possible values are: 0, off; 1, low; 2, normal;
-        /// and 3, high.
-        priority_set, priority);
+        /// Set the maximum PEX message size.  See [`Download::max_size_pex`].
+        set_max_size_pex, max_size_pex);
     d_int_getter!(
         /// Get the size, in bytes, of the torrent contents.
         size_bytes);
@@ -296,10 +1104,32 @@ impl Download {
     d_bool_getter!(
         /// Get the state (`false` is stopped).
         state);
+    d_int_getter!(
+        /// Get the timestamp of the most recent state change (start or stop).
+        state_changed);
+    d_int_getter!(
+        /// Get the number of times the download has been started or stopped.
+        state_counter);
+    d_bool_getter!(
+        /// Did the most recent hash check fail?
+        hashing_failed);
+
+    d_str_getter!(
+        /// Get the name of the throttle group this download is assigned to.  The empty string is
+        /// the default (global) throttle.
+        throttle_name);
+    d_str_setter!(
+        /// Assign this download to a named throttle group.  The download must be stopped while
+        /// its throttle group is changed.
+        set_throttle_name, throttle_name);
 
     d_str_getter!(
         /// Starts as the file the download was initially created from.
         tied_to_file);
+    d_action!(
+        /// Delete the file this download is tied to (see [`Download::tied_to_file`]), e.g., the
+        /// `.torrent` file in a watch directory, and untie the download from it.
+        delete_tied);
 
     d_int_getter!(
         /// Get the number of trackers associated with this download.
@@ -321,14 +1151,288 @@ impl Download {
         /// Get timestamp the torrent was loaded (or rtorrent was restarted, whichever is more
         /// recent).
         load_date);
+    d_int_getter_named!(
+        /// Get the timestamp at which the download finished (zero if it has not).
+        timestamp_finished, "timestamp.finished");
+    d_int_getter_named!(
+        /// Get the timestamp at which the download was last started.
+        timestamp_started, "timestamp.started");
+
+}
+
+// Decode standard (RFC 4648) base64, ignoring whitespace.  Padding is optional.
+pub(crate) fn base64_decode(encoded: &str) -> Result<Vec<u8>> {
+    let invalid = |what: String| {
+        Err(Error::UnexpectedStructure(format!("invalid base64: {}", what)))
+    };
+    let mut res = Vec::with_capacity(encoded.len() / 4 * 3);
+    let mut bits: u32 = 0;
+    let mut nbits = 0;
+    let mut padded = false;
+    for c in encoded.bytes() {
+        let v = match c {
+            c if c.is_ascii_whitespace() => continue,
+            b'=' => {
+                padded = true;
+                continue;
+            }
+            _ if padded => return invalid(format!("{:?} after padding", c as char)),
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return invalid(format!("byte {:?}", c as char)),
+        };
+        bits = (bits << 6) | v as u32;
+        nbits += 6;
+        if nbits >= 8 {
+            nbits -= 8;
+            res.push((bits >> nbits) as u8);
+        }
+    }
+    // A single leftover character does not encode a whole byte.
+    if nbits >= 6 {
+        return invalid("truncated input".to_owned());
+    }
+    Ok(res)
+}
+
+// The methods whose values `data_path` takes, in order.
+pub(crate) const DATA_PATH_METHODS: &[&str] = &["d.base_path", "d.directory", "d.is_multi_file",
+                                                 "d.name"];
+
+// Determine the data path of a download from the values of `DATA_PATH_METHODS`, whether or not
+// the download is open (`d.base_path` is empty while it is closed), and check that it is safe to
+// delete.
+pub(crate) fn data_path(vals: &[Value], default_directory: &str) -> Result<PathBuf> {
+    let (base_path, directory, multi_file, name) = match vals {
+        [base_path, directory, multi_file, name] => (String::try_from_value(base_path)?,
+                                                     String::try_from_value(directory)?,
+                                                     bool::try_from_value(multi_file)?,
+                                                     String::try_from_value(name)?),
+        other => return Err(Error::UnexpectedStructure(
+            format!("Got {:?}, expected {} data path values", other, DATA_PATH_METHODS.len())
+        )),
+    };
+    let directory = PathBuf::from(directory);
+    let path = if !base_path.is_empty() {
+        PathBuf::from(base_path)
+    } else if multi_file {
+        directory.clone()
+    } else {
+        directory.join(name)
+    };
+    // A single file must be within its directory, not the directory itself.
+    if !multi_file && path == directory {
+        return Err(Error::UnsafePath(path.display().to_string()));
+    }
+    check_data_path(&path, &directory, Path::new(default_directory))?;
+    Ok(path)
+}
+
+// Refuse to delete paths that are empty, relative, or too close to the filesystem root, that lie
+// outside the download's `directory`, or that are not deeper than the server's default directory.
+fn check_data_path(path: &Path, directory: &Path, default_directory: &Path) -> Result<()> {
+    let depth = |path: &Path| {
+        path.components().filter(|component| matches!(component, Component::Normal(_))).count()
+    };
+    let safe = path.is_absolute()
+        && path.components().all(|component| {
+            matches!(component, Component::RootDir | Component::Normal(_))
+        })
+        && depth(path) >= 2
+        && path.starts_with(directory)
+        && depth(path) > depth(default_directory);
+    if !safe {
+        return Err(Error::UnsafePath(path.display().to_string()));
+    }
+    Ok(())
+}
+
+// ruTorrent stores labels percent-encoded (as by PHP's `rawurlencode`).
+pub(crate) fn label_encode(label: &str) -> String {
+    let mut res = String::with_capacity(label.len());
+    for b in label.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                res.push(b as char)
+            }
+            _ => res.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    res
+}
 
+// Decode a percent-encoded label.  Malformed escapes are passed through unmodified.
+pub(crate) fn label_decode(raw: &str) -> String {
+    let bytes = raw.as_bytes();
+    let mut res = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = match bytes.get(i..i + 3) {
+            Some([b'%', hi, lo]) if hi.is_ascii_hexdigit() && lo.is_ascii_hexdigit() => {
+                std::str::from_utf8(&bytes[i + 1..i + 3])
+                    .ok()
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            }
+            _ => None,
+        };
+        match escaped {
+            Some(b) => {
+                res.push(b);
+                i += 3;
+            }
+            None => {
+                res.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&res).into_owned()
 }
 
 unsafe impl Send for Download {}
 unsafe impl Sync for Download {}
 
+/// Displays the download as its infohash.  See [`Download::describe`] for a description
+/// including its name.
+///
+/// [`Download::describe`]: crate::Download::describe
+impl fmt::Display for Download {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.sha1_hex())
+    }
+}
+
+// Downloads are identified by server endpoint and infohash.
+impl PartialEq for Download {
+    fn eq(&self, other: &Self) -> bool {
+        self.endpoint() == other.endpoint() && self.sha1_hex() == other.sha1_hex()
+    }
+}
+
+impl Eq for Download {}
+
+impl Hash for Download {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.endpoint().hash(state);
+        self.sha1_hex().hash(state);
+    }
+}
+
+impl PartialOrd for Download {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Download {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.endpoint(), self.sha1_hex()).cmp(&(other.endpoint(), other.sha1_hex()))
+    }
+}
+
 impl From<&Download> for Value {
     fn from(dl: &Download) -> Self {
         Value::String(dl.inner.sha1_hex.to_owned())
     }
 }
+
+impl ToValue for Download {
+    fn to_value(&self) -> Value {
+        Value::from(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path_values(base_path: &str, directory: &str, multi_file: bool, name: &str) -> Vec<Value> {
+        vec![Value::from(base_path), Value::from(directory), Value::from(multi_file),
+             Value::from(name)]
+    }
+
+    #[test]
+    fn base64_vectors() {
+        // The test vectors of RFC 4648, section 10.
+        for (encoded, decoded) in &[("", ""), ("Zg==", "f"), ("Zm8=", "fo"), ("Zm9v", "foo"),
+                                    ("Zm9vYg==", "foob"), ("Zm9vYmE=", "fooba"),
+                                    ("Zm9vYmFy", "foobar")] {
+            assert_eq!(base64_decode(encoded).unwrap(), decoded.as_bytes(), "{}", encoded);
+        }
+        assert_eq!(base64_decode("+/+/").unwrap(), [0xfb, 0xff, 0xbf]);
+    }
+
+    #[test]
+    fn base64_padding() {
+        // Padding is optional, and whitespace (e.g., line breaks) is ignored.
+        assert_eq!(base64_decode("Zg").unwrap(), b"f");
+        assert_eq!(base64_decode("Zm8").unwrap(), b"fo");
+        assert_eq!(base64_decode("Zm9v\nYmFy\r\n").unwrap(), b"foobar");
+        assert_eq!(base64_decode("Zm9vYg= =\n").unwrap(), b"foob");
+    }
+
+    #[test]
+    fn base64_invalid() {
+        for encoded in &["Zm9v!", "Zm9v-_", "Zg==Zg==", "Zm=9", "Z", "Zm9vY"] {
+            assert!(matches!(base64_decode(encoded), Err(Error::UnexpectedStructure(_))),
+                    "{}", encoded);
+        }
+    }
+
+    #[test]
+    fn label_round_trip() {
+        for (label, encoded) in &[("Movies", "Movies"), ("TV Shows", "TV%20Shows"),
+                                  ("100%", "100%25"), ("a+b", "a%2Bb"), ("a/b&c", "a%2Fb%26c"),
+                                  ("Séries", "S%C3%A9ries"), ("日本", "%E6%97%A5%E6%9C%AC"),
+                                  ("a-b_c.d~", "a-b_c.d~"), ("", "")] {
+            assert_eq!(label_encode(label), *encoded, "{}", label);
+            assert_eq!(label_decode(encoded), *label, "{}", encoded);
+        }
+    }
+
+    #[test]
+    fn label_decode_lenient() {
+        // Unencoded labels (e.g., set by other tools) pass through; `+` is not a space.
+        assert_eq!(label_decode("TV Shows"), "TV Shows");
+        assert_eq!(label_decode("a+b"), "a+b");
+        assert_eq!(label_decode("Séries"), "Séries");
+        // Malformed escapes are passed through unmodified.
+        assert_eq!(label_decode("100%"), "100%");
+        assert_eq!(label_decode("%zz%4"), "%zz%4");
+        assert_eq!(label_decode("%41%4a"), "AJ");
+    }
+
+    #[test]
+    fn data_path_open_and_closed() {
+        let open = path_values("/data/dl/file.iso", "/data/dl", false, "file.iso");
+        assert_eq!(data_path(&open, "/data").unwrap(), Path::new("/data/dl/file.iso"));
+        let single = path_values("", "/data/dl", false, "file.iso");
+        assert_eq!(data_path(&single, "/data").unwrap(), Path::new("/data/dl/file.iso"));
+        let multi = path_values("", "/data/dl/Name", true, "Name");
+        assert_eq!(data_path(&multi, "/data").unwrap(), Path::new("/data/dl/Name"));
+    }
+
+    #[test]
+    fn data_path_unsafe() {
+        for (values, default_directory) in &[
+            // The default directory itself.
+            (path_values("", "/data/dl", true, "Name"), "/data/dl"),
+            (path_values("/data/dl", "/data/dl", true, "Name"), "/data/dl"),
+            // Outside the download's directory.
+            (path_values("/data/other/file.iso", "/data/dl", false, "file.iso"), "/data"),
+            // A single file's directory, rather than the file.
+            (path_values("", "/data/dl", false, ""), "/data"),
+            (path_values("", "/data/dl", false, "../dl"), "/data"),
+            // Relative, or too shallow.
+            (path_values("", "data/dl", true, "Name"), ""),
+            (path_values("", "/data", true, "Name"), ""),
+            (path_values("", "/", false, "data"), ""),
+        ] {
+            assert!(matches!(data_path(values, default_directory), Err(Error::UnsafePath(_))),
+                    "{:?}", values);
+        }
+    }
+}
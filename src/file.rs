@@ -6,9 +6,18 @@ This module defines the [`File`] type and support code.
 !*/
 
 use crate::macros::*;
-use crate::{Download, Result};
+use crate::{Download, Error, Result, ToValue};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
-use xmlrpc::{Request, Value};
+use xmlrpc::Value;
+
+macro_rules! f_action_named {
+    ($(#[$meta:meta])* $method: ident, $apimethod: literal) => {
+        prim_getter_named!($(#[$meta])* "f.", $method, (), $apimethod);
+    }
+}
 
 macro_rules! f_int_getter {
     ($(#[$meta:meta])* $method: ident) => {
@@ -22,12 +31,126 @@ macro_rules! f_str_getter {
     }
 }
 
+macro_rules! f_bool_getter {
+    ($(#[$meta:meta])* $method: ident) => {
+        prim_getter!($(#[$meta])* "f.", $method, bool);
+    }
+}
+
+macro_rules! f_action {
+    ($(#[$meta:meta])* $method: ident) => {
+        prim_getter!($(#[$meta])* "f.", $method, ());
+    }
+}
+
 macro_rules! f_int_setter {
     ($(#[$meta:meta])* $set_method: ident, $apimethod: ident) => {
         prim_setter!($(#[$meta])* "f.", $set_method, $apimethod, i64);
     }
 }
 
+/// The priority of a [`File`]
+///
+/// [`File`]: crate::File
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum FilePriority {
+    /// Do not download.
+    Off,
+    Normal,
+    /// Prioritize this file's chunks over "Normal" files.
+    High,
+}
+
+impl TryFromValue for FilePriority {
+    fn try_from_value(val: &Value) -> Result<Self> {
+        match i64::try_from_value(val)? {
+            0 => Ok(FilePriority::Off),
+            1 => Ok(FilePriority::Normal),
+            2 => Ok(FilePriority::High),
+            _ => Err(Error::UnexpectedStructure(
+                format!("Got {:?}, expected file priority (0-2)", val)
+            )),
+        }
+    }
+}
+
+impl From<FilePriority> for Value {
+    fn from(prio: FilePriority) -> Self {
+        Value::Int64(prio as i64)
+    }
+}
+
+impl ToValue for FilePriority {
+    fn to_value(&self) -> Value {
+        Value::from(*self)
+    }
+}
+
+/// The completion of a single [`File`], as returned by [`Download::file_progress`]
+///
+/// [`Download::file_progress`]: crate::Download::file_progress
+#[derive(Clone, Debug)]
+pub struct FileProgress {
+    /// The file.
+    pub file: File,
+    /// The path of the file, relative to the download's base path.
+    pub path: String,
+    /// The size of the file, in bytes.
+    pub size_bytes: i64,
+    /// The number of bytes of the file contained in completed chunks.
+    pub completed_bytes: i64,
+}
+
+impl FileProgress {
+    /// The completed fraction of the file, from `0.0` to `1.0`.  Empty files are complete.
+    pub fn completion(&self) -> f64 {
+        if self.size_bytes == 0 {
+            1.
+        } else {
+            self.completed_bytes as f64 / self.size_bytes as f64
+        }
+    }
+}
+
+/// Commonly displayed information about a single [`File`], as returned by
+/// [`Download::file_summaries`]
+///
+/// [`Download::file_summaries`]: crate::Download::file_summaries
+#[derive(Clone, Debug)]
+pub struct FileSummary {
+    /// The file.
+    pub file: File,
+    /// The path of the file, relative to the download's base path.
+    pub path: String,
+    /// The size of the file, in bytes.
+    pub size_bytes: i64,
+    /// The priority of the file.
+    pub priority: FilePriority,
+    /// The number of completed chunks associated with this file (including chunks that only
+    /// partially include this file).
+    pub completed_chunks: i64,
+    /// The number of chunks associated with this file.
+    pub size_chunks: i64,
+}
+
+impl FileSummary {
+    /// The completed fraction of the file's chunks, from `0.0` to `1.0`.  Empty files are
+    /// complete.
+    ///
+    /// Chunks shared with neighboring files count as complete for both; see
+    /// [`Download::file_progress`] for byte-accurate completion.
+    ///
+    /// [`Download::file_progress`]: crate::Download::file_progress
+    pub fn completion(&self) -> f64 {
+        if self.size_chunks == 0 {
+            1.
+        } else {
+            self.completed_chunks as f64 / self.size_chunks as f64
+        }
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct FileInner {
     download: Download,
@@ -87,6 +210,20 @@ impl File {
         Self { inner: Arc::new(FileInner { download, index, }) }
     }
 
+    /// Construct a File representing the file at `index` (counting from zero, in the order
+    /// returned by [`Download::files`] or an `f.*` multicall) in `download`.  This constructor
+    /// does not validate that the file exists.
+    ///
+    /// [`Download::files`]: crate::Download::files
+    pub fn from_index(download: &Download, index: i64) -> Self {
+        Self::new(download.clone(), index)
+    }
+
+    /// Get the index of this file within its download.
+    pub fn index(&self) -> i64 {
+        self.inner.index
+    }
+
     #[inline]
     pub(crate) fn endpoint(&self) -> &str {
         self.inner.download.endpoint()
@@ -99,12 +236,48 @@ impl File {
     f_str_getter!(
         /// Get the absolute path of this file.
         frozen_path);
+
+    f_bool_getter!(
+        /// Is this file queued to be created (allocated) when the download is next opened?
+        is_create_queued);
+    f_action!(
+        /// Queue this file to be created when the download is next opened.
+        set_create_queued);
+    f_action!(
+        /// Do not create this file when the download is next opened (e.g., to avoid
+        /// preallocating a file that will not be downloaded).
+        unset_create_queued);
+    f_bool_getter!(
+        /// Is this file queued to be resized (truncated or extended) when the download is next
+        /// opened?
+        is_resize_queued);
+    f_action!(
+        /// Queue this file to be resized when the download is next opened.
+        set_resize_queued);
+    f_action!(
+        /// Do not resize this file when the download is next opened.
+        unset_resize_queued);
+    f_int_getter!(
+        /// The time rtorrent last wrote to (touched) this file, in microseconds since Unix epoch.
+        last_touched);
+    f_int_getter!(
+        /// The number of leading path components this file shares with the next file in the
+        /// download.
+        match_depth_next);
+    f_int_getter!(
+        /// The number of leading path components this file shares with the previous file in the
+        /// download.
+        match_depth_prev);
     f_int_getter!(
         /// The offset (in bytes) of the file from the start of the torrent data.
         offset);
     f_str_getter!(
         /// Get the path of this file, relative to the download's base path.
         path);
+    prim_getter!(
+        /// Get the path of this file, relative to the download's base path, as a list of
+        /// components.
+        "f.", path_components, Vec<String>);
 
     f_int_getter!(
         /// The priority of the file.
@@ -116,6 +289,38 @@ impl File {
     f_int_setter!(
         /// Set the priority of the file.  See [`File::priority`].
         set_priority, priority);
+    prim_getter_named!(
+        /// The priority of the file, as a [`FilePriority`].
+        ///
+        /// [`FilePriority`]: crate::FilePriority
+        "f.", priority_level, FilePriority, "priority");
+    prim_setter!(
+        /// Set the priority of the file.  The new priority takes effect once
+        /// [`Download::update_priorities`] is invoked.
+        ///
+        /// [`Download::update_priorities`]: crate::Download::update_priorities
+        "f.", set_priority_level, priority, FilePriority);
+
+    f_bool_getter!(
+        /// Are the first chunks of this file downloaded before other chunks?
+        prioritize_first);
+    f_action_named!(
+        /// Download the first chunks of this file before other chunks (e.g., for media
+        /// streaming).
+        enable_prioritize_first, "prioritize_first.enable");
+    f_action_named!(
+        /// Stop prioritizing the first chunks of this file.
+        disable_prioritize_first, "prioritize_first.disable");
+    f_bool_getter!(
+        /// Are the last chunks of this file downloaded before other chunks?
+        prioritize_last);
+    f_action_named!(
+        /// Download the last chunks of this file before other chunks (e.g., for media containers
+        /// with trailing indexes).
+        enable_prioritize_last, "prioritize_last.enable");
+    f_action_named!(
+        /// Stop prioritizing the last chunks of this file.
+        disable_prioritize_last, "prioritize_last.disable");
 
     f_int_getter!(
         /// Get the size of the file, in bytes.
@@ -130,8 +335,53 @@ impl File {
 unsafe impl Send for File {}
 unsafe impl Sync for File {}
 
+/// Displays the file as rtorrent's identifier for it (e.g., `"<infohash>:f0"`).  See
+/// [`File::path`] for its path.
+///
+/// [`File::path`]: crate::File::path
+impl fmt::Display for File {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:f{}", self.inner.download.sha1_hex(), self.inner.index)
+    }
+}
+
+// Files are identified by their download and index.
+impl PartialEq for File {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner.download == other.inner.download && self.inner.index == other.inner.index
+    }
+}
+
+impl Eq for File {}
+
+impl Hash for File {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner.download.hash(state);
+        self.inner.index.hash(state);
+    }
+}
+
+impl PartialOrd for File {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for File {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.inner.download, &self.inner.index)
+            .cmp(&(&other.inner.download, &other.inner.index))
+    }
+}
+
 impl From<&File> for Value {
     fn from(file: &File) -> Self {
         Value::String(format!("{}:f{}", &file.inner.download.sha1_hex(), file.inner.index))
     }
 }
+
+impl ToValue for File {
+    fn to_value(&self) -> Value {
+        Value::from(self)
+    }
+}
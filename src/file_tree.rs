@@ -0,0 +1,142 @@
+/*! Torrent file trees
+
+This module defines the [`FileTree`] type and support code.
+
+[`FileTree`]: crate::FileTree
+!*/
+
+use crate::multicall::f;
+use crate::{Download, File, FilePriority, Result};
+use std::collections::BTreeMap;
+
+/// A file within a [`FileTree`]
+///
+/// [`FileTree`]: crate::FileTree
+#[derive(Clone, Debug)]
+pub struct FileTreeEntry {
+    /// The file.
+    pub file: File,
+    /// The final component of the file's path.
+    pub name: String,
+    /// The size of the file, in bytes.
+    pub size_bytes: i64,
+    /// The (approximate) number of completed bytes.  See [`FileTree`].
+    ///
+    /// [`FileTree`]: crate::FileTree
+    pub completed_bytes: i64,
+    /// The priority of the file.
+    pub priority: FilePriority,
+}
+
+/// The contents of a [`Download`] as a nested directory structure
+///
+/// Each `FileTree` represents a directory; the root represents the download's base path.
+/// Directory sizes and completion are the aggregate of everything beneath them.
+///
+/// Completion is derived from per-file chunk counts.  Chunks that span file boundaries are
+/// counted in full toward each file they touch, so completion is approximate; see
+/// [`Download::file_progress`] for exact per-file completion.
+///
+/// ```no_run
+/// # use rtorrent_xmlrpc_bindings as rtorrent;
+/// use rtorrent::{Download, FileTree};
+///
+/// fn print_tree(tree: &FileTree, depth: usize) {
+///     for (name, dir) in &tree.dirs {
+///         let pct = dir.completion() * 100.;
+///         println!("{:indent$}{}/ ({:.0}%)", "", name, pct, indent = depth * 2);
+///         print_tree(dir, depth + 1);
+///     }
+///     for file in &tree.files {
+///         println!("{:indent$}{} ({} bytes)", "", file.name, file.size_bytes, indent = depth * 2);
+///     }
+/// }
+///
+/// fn show(dl: &Download) -> rtorrent::Result<()> {
+///     print_tree(&dl.file_tree()?, 0);
+///     Ok(())
+/// }
+/// ```
+///
+/// [`Download`]: crate::Download
+/// [`Download::file_progress`]: crate::Download::file_progress
+#[derive(Clone, Debug, Default)]
+pub struct FileTree {
+    /// The total size of all files in this directory (recursively), in bytes.
+    pub size_bytes: i64,
+    /// The (approximate) number of completed bytes in this directory (recursively).
+    pub completed_bytes: i64,
+    /// Subdirectories, by name.
+    pub dirs: BTreeMap<String, FileTree>,
+    /// Files directly contained in this directory.
+    pub files: Vec<FileTreeEntry>,
+}
+
+impl FileTree {
+    pub(crate) fn fetch(download: &Download) -> Result<Self> {
+        let mut root = FileTree::default();
+        let rows = f::MultiBuilder::new(download.server(), download.sha1_hex(), None)
+            .call(f::PATH_COMPONENTS)
+            .call(f::SIZE_BYTES)
+            .call(f::SIZE_CHUNKS)
+            .call(f::COMPLETED_CHUNKS)
+            .call(f::PRIORITY_LEVEL)
+            .invoke()?;
+        for (index, (mut components, size_bytes, size_chunks, completed_chunks, priority)) in
+            rows.into_iter().enumerate() {
+            let completed_bytes = if size_chunks > 0 {
+                (size_bytes as i128 * completed_chunks as i128 / size_chunks as i128) as i64
+            } else {
+                size_bytes
+            };
+            let name = components.pop().unwrap_or_default();
+            let entry = FileTreeEntry {
+                file: File::new(download.clone(), index as i64),
+                name,
+                size_bytes,
+                completed_bytes,
+                priority,
+            };
+            root.insert(&components, entry);
+        }
+        Ok(root)
+    }
+
+    fn insert(&mut self, dirs: &[String], entry: FileTreeEntry) {
+        self.size_bytes += entry.size_bytes;
+        self.completed_bytes += entry.completed_bytes;
+        match dirs.split_first() {
+            Some((first, rest)) => {
+                self.dirs.entry(first.clone()).or_default().insert(rest, entry)
+            }
+            None => self.files.push(entry),
+        }
+    }
+
+    /// The completed fraction of this directory, from `0.0` to `1.0`.  Empty directories are
+    /// complete.
+    pub fn completion(&self) -> f64 {
+        if self.size_bytes == 0 {
+            1.
+        } else {
+            self.completed_bytes as f64 / self.size_bytes as f64
+        }
+    }
+
+    /// Iterate over every file in this directory, recursively, along with its path relative to
+    /// this directory.
+    pub fn walk(&self) -> Vec<(String, &FileTreeEntry)> {
+        let mut res = Vec::new();
+        self.walk_into("", &mut res);
+        res
+    }
+
+    fn walk_into<'a>(&'a self, prefix: &str, res: &mut Vec<(String, &'a FileTreeEntry)>) {
+        for (name, dir) in &self.dirs {
+            dir.walk_into(&format!("{}{}/", prefix, name), res);
+        }
+        for file in &self.files {
+            res.push((format!("{}{}", prefix, file.name), file));
+        }
+    }
+}
@@ -0,0 +1,119 @@
+/*! Ratio groups
+
+This module defines the [`RatioGroup`] type and support code.
+
+[`RatioGroup`]: crate::RatioGroup
+!*/
+
+use crate::{call_method, Result, Server, TryFromValue};
+use std::sync::Arc;
+use xmlrpc::Value;
+
+#[derive(Debug)]
+pub(crate) struct RatioGroupInner {
+    name: String,
+    server: Server,
+}
+
+/// A `RatioGroup` represents a set of seeding rules applied to the downloads in some view
+///
+/// Accessors on `RatioGroup` correspond to the `group.*` and `group2.*` rtorrent APIs.  When
+/// ratio handling is enabled for a group, rtorrent periodically checks each download in the
+/// group's view.  Once a download's ratio exceeds the configured minimum and it has uploaded the
+/// configured amount (or its ratio exceeds the configured maximum), the group's ratio command is
+/// run against it.
+///
+/// rtorrent has a built-in group named `"seeding"`.
+///
+/// # Examples
+///
+/// Stop downloads in the "seeding" view once they reach a ratio of 2.0:
+///
+/// ```no_run
+/// use rtorrent_xmlrpc_bindings as rtorrent;
+///
+/// let my_handle = rtorrent::Server::new("http://1.2.3.4/RPC2");
+/// let group = my_handle.ratio_group("seeding");
+/// group.set_min(200)?;
+/// group.set_max(200)?;
+/// group.set_command("d.stop=")?;
+/// group.enable()?;
+/// # Ok::<(), rtorrent::Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct RatioGroup {
+    inner: Arc<RatioGroupInner>,
+}
+
+impl RatioGroup {
+    pub(crate) fn new(server: &Server, name: &str) -> Self {
+        let server = server.clone();
+        Self { inner: Arc::new(RatioGroupInner { server, name: name.to_owned() }) }
+    }
+
+    /// Get the name of this group.
+    pub fn name(&self) -> &str {
+        &self.inner.name
+    }
+
+    fn call<T: TryFromValue>(&self, ns: &str, method: &str, arg: Option<Value>) -> Result<T> {
+        let api = format!("{}.{}.{}", ns, self.inner.name, method);
+        let mut args = vec![Value::from("")];
+        args.extend(arg);
+        call_method(self.inner.server.endpoint(), &api, args)
+    }
+
+    /// Enable ratio handling for this group.
+    pub fn enable(&self) -> Result<()> {
+        self.call("group", "ratio.enable", None)
+    }
+
+    /// Disable ratio handling for this group.
+    pub fn disable(&self) -> Result<()> {
+        self.call("group", "ratio.disable", None)
+    }
+
+    /// Get the minimum ratio (in percent, e.g., `200` for 2.0) a download must reach before the
+    /// ratio command is considered.
+    pub fn min(&self) -> Result<i64> {
+        self.call("group2", "ratio.min", None)
+    }
+
+    /// Set the minimum ratio (in percent).  See [`RatioGroup::min`].
+    pub fn set_min(&self, new: i64) -> Result<()> {
+        self.call("group2", "ratio.min.set", Some(new.into()))
+    }
+
+    /// Get the maximum ratio (in percent).  Downloads exceeding this ratio have the ratio command
+    /// run regardless of the upload amount.  Zero disables the maximum.
+    pub fn max(&self) -> Result<i64> {
+        self.call("group2", "ratio.max", None)
+    }
+
+    /// Set the maximum ratio (in percent).  See [`RatioGroup::max`].
+    pub fn set_max(&self, new: i64) -> Result<()> {
+        self.call("group2", "ratio.max.set", Some(new.into()))
+    }
+
+    /// Get the amount (bytes) a download must have uploaded, in addition to reaching the minimum
+    /// ratio, before the ratio command is run.
+    pub fn upload(&self) -> Result<i64> {
+        self.call("group2", "ratio.upload", None)
+    }
+
+    /// Set the required upload amount (bytes).  See [`RatioGroup::upload`].
+    pub fn set_upload(&self, new: i64) -> Result<()> {
+        self.call("group2", "ratio.upload.set", Some(new.into()))
+    }
+
+    /// Set the command run against each download that satisfies this group's ratio rules, e.g.,
+    /// `"d.stop="` or `"d.close= ; d.erase="`.
+    pub fn set_command(&self, command: &str) -> Result<()> {
+        let api = format!("group.{}.ratio.command", self.inner.name);
+        call_method(self.inner.server.endpoint(), "method.set",
+                    vec![Value::from(""), Value::from(api), Value::from(command)])
+    }
+}
+
+unsafe impl Send for RatioGroup {}
+unsafe impl Sync for RatioGroup {}
@@ -0,0 +1,215 @@
+/*! Rate-limited hash checking
+
+This module defines the [`HashCheckQueue`] type and support code.
+
+[`HashCheckQueue`]: crate::HashCheckQueue
+!*/
+
+use crate::multicall::system::SystemMultiCall;
+use crate::{Download, Result, Server, TryFromValue};
+use std::collections::VecDeque;
+use std::thread;
+use std::time::Duration;
+use xmlrpc::Value;
+
+// The getters polled for each download being checked, in order.
+const POLL_METHODS: &[&str] = &["d.hashing", "d.is_hash_checking", "d.chunks_hashed",
+                                "d.size_chunks", "d.complete"];
+
+/// Progress reported by [`HashCheckQueue::run`]
+///
+/// [`HashCheckQueue::run`]: crate::HashCheckQueue::run
+#[derive(Clone, Debug)]
+pub enum HashCheckEvent {
+    /// A hash check of the download was started.
+    Started(Download),
+    /// A download is being checked: `chunks_hashed` of its `size_chunks` chunks have been
+    /// verified so far.
+    Progress {
+        download: Download,
+        chunks_hashed: i64,
+        size_chunks: i64,
+    },
+    /// The hash check of a download finished.  `complete` is whether all of its data was found
+    /// to be present and correct.
+    Finished {
+        download: Download,
+        complete: bool,
+    },
+    /// The download was erased from rtorrent before its hash check finished.
+    Removed(Download),
+}
+
+/// `HashCheckQueue` hash checks many downloads, a few at a time
+///
+/// Starting `d.check_hash` on many downloads at once makes rtorrent read all of their data
+/// concurrently.  A `HashCheckQueue` instead checks at most a fixed number of downloads at a
+/// time: it polls the downloads being checked, and starts the next queued download as each check
+/// finishes.
+///
+/// ```no_run
+/// use rtorrent_xmlrpc_bindings as rtorrent;
+/// use rtorrent::{HashCheckEvent, HashCheckQueue};
+///
+/// let my_handle = rtorrent::Server::new("http://1.2.3.4/RPC2");
+///
+/// let mut queue = HashCheckQueue::new(&my_handle, 2);
+/// for download in my_handle.download_list()? {
+///     queue.push(download);
+/// }
+/// queue.run(|event| match event {
+///     HashCheckEvent::Finished { download, complete } => {
+///         println!("{}: {}", download.sha1_hex(), if complete { "OK" } else { "incomplete" });
+///     }
+///     _ => {}
+/// })?;
+/// # Ok::<(), rtorrent::Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct HashCheckQueue {
+    server: Server,
+    concurrency: usize,
+    poll_interval: Duration,
+    queued: VecDeque<Download>,
+    active: Vec<Download>,
+}
+
+impl HashCheckQueue {
+    /// Create an empty queue checking at most `concurrency` (at least one) downloads at a time.
+    pub fn new(server: &Server, concurrency: usize) -> Self {
+        Self {
+            server: server.clone(),
+            concurrency: concurrency.max(1),
+            poll_interval: Duration::from_secs(1),
+            queued: VecDeque::new(),
+            active: Vec::new(),
+        }
+    }
+
+    /// Return a queue that polls the downloads being checked every `interval`, rather than every
+    /// second.
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Add `download` to the end of the queue.
+    pub fn push(&mut self, download: Download) {
+        self.queued.push_back(download);
+    }
+
+    /// Get the number of downloads queued or being checked.
+    pub fn len(&self) -> usize {
+        self.queued.len() + self.active.len()
+    }
+
+    /// Is the queue empty (nothing queued or being checked)?
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Check every queued download, passing progress to `callback`, and return once all checks
+    /// have finished.
+    ///
+    /// Each poll is a single round trip, regardless of the number of downloads being checked.
+    /// Downloads erased while queued or being checked are dropped from the queue, and reported as
+    /// [`Removed`].  If an error is returned, the queue keeps its state, and `run` may be called
+    /// again to continue.
+    ///
+    /// [`Removed`]: crate::HashCheckEvent::Removed
+    pub fn run<F>(&mut self, mut callback: F) -> Result<()>
+        where F: FnMut(HashCheckEvent) {
+        loop {
+            self.start_next(&mut callback)?;
+            if self.active.is_empty() {
+                return Ok(());
+            }
+            thread::sleep(self.poll_interval);
+            self.poll(&mut callback)?;
+        }
+    }
+
+    // Start checks until `concurrency` downloads are being checked, in a single round trip.
+    fn start_next<F>(&mut self, callback: &mut F) -> Result<()>
+        where F: FnMut(HashCheckEvent) {
+        let count = self.concurrency.saturating_sub(self.active.len()).min(self.queued.len());
+        if count == 0 {
+            return Ok(());
+        }
+        let mut mc = SystemMultiCall::new();
+        for download in self.queued.iter().take(count) {
+            mc.push("d.check_hash", vec![Value::from(download)]);
+        }
+        let results = mc.invoke_each(&self.server)?;
+
+        // Downloads whose check could not be started for another reason stay queued.
+        let mut first_error = None;
+        let mut retry = Vec::new();
+        for (download, result) in self.queued.drain(..count).zip(results) {
+            match result {
+                Ok(_) => {
+                    callback(HashCheckEvent::Started(download.clone()));
+                    self.active.push(download);
+                }
+                Err(e) if e.is_not_found() => callback(HashCheckEvent::Removed(download)),
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                    retry.push(download);
+                }
+            }
+        }
+        for download in retry.into_iter().rev() {
+            self.queued.push_front(download);
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+
+    // Poll the downloads being checked, and retire those that have finished.
+    fn poll<F>(&mut self, callback: &mut F) -> Result<()>
+        where F: FnMut(HashCheckEvent) {
+        let mut mc = SystemMultiCall::new();
+        for download in &self.active {
+            for method in POLL_METHODS {
+                mc.push(method, vec![Value::from(download)]);
+            }
+        }
+        let mut results = mc.invoke_each(&self.server)?.into_iter();
+
+        // Classify every download before reporting any, so that an error leaves the queue as it
+        // was.
+        let mut events = Vec::new();
+        for download in &self.active {
+            let results = results.by_ref().take(POLL_METHODS.len()).collect::<Vec<_>>();
+            let vals = match results.into_iter().collect::<Result<Vec<_>>>() {
+                Ok(vals) => vals,
+                Err(e) if e.is_not_found() => {
+                    events.push(HashCheckEvent::Removed(download.clone()));
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            let hashing = bool::try_from_value(&vals[0])? || bool::try_from_value(&vals[1])?;
+            events.push(if hashing {
+                HashCheckEvent::Progress {
+                    download: download.clone(),
+                    chunks_hashed: i64::try_from_value(&vals[2])?,
+                    size_chunks: i64::try_from_value(&vals[3])?,
+                }
+            } else {
+                HashCheckEvent::Finished {
+                    download: download.clone(),
+                    complete: bool::try_from_value(&vals[4])?,
+                }
+            });
+        }
+
+        self.active.clear();
+        for event in events {
+            if let HashCheckEvent::Progress { download, .. } = &event {
+                self.active.push(download.clone());
+            }
+            callback(event);
+        }
+        Ok(())
+    }
+}
@@ -45,6 +45,12 @@ d::MultiBuilder::new(&my_handle, "default")
 # Ok::<(), rtorrent::Error>(())
 ```
 
+## Optional Features
+
+* `serde`: Implement `Serialize` and `Deserialize` for plain data types, such as [`Stats`] and
+  [`DownloadSummary`], and enable deserializing multicall rows into user types with
+  `invoke_into()`.
+
 ## Current Limitations
 
 * Some XMLRPC APIs are not yet wrapped by this crate.
@@ -52,35 +58,256 @@ d::MultiBuilder::new(&my_handle, "default")
 [rtorrent]: https://rakshasa.github.io/rtorrent/
 [XMLRPC API]: https://rtorrent-docs.readthedocs.io/en/latest/cmd-ref.html
 
+[`DownloadSummary`]: crate::DownloadSummary
 [`Error`]: crate::Error
 [`multicall`]: crate::multicall
 [`Server`]: crate::Server
+[`Stats`]: crate::Stats
 !*/
 
-use std::sync::Arc;
-use xmlrpc::{Request, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::net::IpAddr;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use xmlrpc::Request;
 
 pub(crate) mod value_conversion;
+pub mod bencode;
+mod cache;
 mod download;
 mod file;
+mod file_tree;
+mod group;
+mod hash_check;
+mod manager;
 pub mod multicall;
 mod peer;
+mod policy;
+mod query;
+mod report;
+pub mod rules;
+mod schedule;
+mod session;
+mod sha1;
+mod stats;
+mod timestamp;
 mod tracker;
+pub mod watch;
+mod watch_dir;
 
-pub use download::Download;
-pub use file::File;
-pub use peer::Peer;
-pub use tracker::Tracker;
-pub use value_conversion::TryFromValue;
+pub use cache::CachedServer;
+pub use download::{Download, DownloadStatus, DownloadSummary, Priority, SwarmInfo};
+pub use file::{File, FilePriority, FileProgress, FileSummary};
+pub use file_tree::{FileTree, FileTreeEntry};
+pub use group::RatioGroup;
+pub use hash_check::{HashCheckEvent, HashCheckQueue};
+pub use manager::TorrentManager;
+pub use peer::{Peer, PeerSummary, SwarmOverview};
+pub use policy::{SeedingAction, SeedingDecision, SeedingPolicy, SeedingRule};
+pub use query::Query;
+pub use report::{DiskUsage, DiskUsageReport, GroupBy, TrackerHealth, TrackerHealthEntry,
+                 TrackerHealthReport};
+pub use schedule::{ThrottleRule, ThrottleSchedule, ThrottleTarget};
+pub use session::{DownloadBackup, SessionBackup};
+pub use stats::Stats;
+pub use timestamp::Timestamp;
+pub use tracker::{Tracker, TrackerSummary, TrackerType};
+pub use value_conversion::{ToValue, TryFromValue};
+pub use watch_dir::{WatchDir, WatchDirEvent};
+#[doc(hidden)]
+pub use value_conversion::struct_field as __struct_field;
+/// The XMLRPC value type results are converted from (re-exported from the `xmlrpc` crate).
+pub use xmlrpc::Value;
 
 /// The canonical [`Result`] for this crate (we return the same error type everywhere).
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// The unified error type for this crate.
+///
+/// Every error arising from an XMLRPC call is wrapped in [`Error::Call`], which records the
+/// method, target, and (for multicalls) column involved.  Use [`kind`] to classify an error, or
+/// [`without_context`] to inspect the underlying variant; e.g., match
+/// `err.without_context()` against `Error::XmlRpc(_)`, rather than `err` itself.
+///
+/// (Before version 2.0, many calls returned the underlying variant without this wrapping.)
+///
+/// [`Error::Call`]: crate::Error::Call
+/// [`kind`]: crate::Error::kind
+/// [`without_context`]: crate::Error::without_context
+#[non_exhaustive]
 #[derive(Debug)]
 pub enum Error {
     XmlRpc(xmlrpc::Error),
     UnexpectedStructure(String),
+    /// A single call within a batched `system.multicall` failed.
+    Fault(xmlrpc::Fault),
+    /// A local I/O operation failed.
+    Io(std::io::Error),
+    /// Torrent metainfo (`.torrent` file contents) could not be parsed.
+    InvalidTorrent(String),
+    /// An operation did not complete within the allotted time.
+    Timeout(String),
+    /// A path was refused because operating on it could be destructive (e.g., `/`).
+    UnsafePath(String),
+    /// A string is not a valid infohash (40 hex digits).
+    InvalidInfohash(String),
+    /// No download with the given infohash is loaded on the server.
+    DownloadNotFound(String),
+    /// A query refers to an XMLRPC method the server does not support.
+    UnsupportedMethod(String),
+    /// A download's data is incomplete (e.g., after a hash check).
+    Incomplete(String),
+    /// An error occurred in a particular XMLRPC call, described by the [`CallContext`].
+    ///
+    /// [`CallContext`]: crate::CallContext
+    Call(CallContext, Box<Error>),
+}
+
+/// The XMLRPC call an [`Error`] occurred in
+///
+/// [`Error`]: crate::Error
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CallContext {
+    /// The XMLRPC method called (e.g., `"d.name"` or `"d.multicall2"`).
+    pub method: String,
+    /// The target of the call (e.g., a download infohash, or `"HASH:f0"` for a file), if any.
+    pub target: Option<String>,
+    /// For multicalls, the zero-based index of the column whose value could not be converted.
+    pub column: Option<usize>,
+}
+
+impl CallContext {
+    pub(crate) fn new(method: &str, target: Option<&Value>) -> Self {
+        let target = match target {
+            Some(Value::String(s)) if !s.is_empty() => Some(s.clone()),
+            _ => None,
+        };
+        Self { method: method.to_owned(), target, column: None }
+    }
+
+    pub(crate) fn with_column(&self, column: usize) -> Self {
+        Self { column: Some(column), ..self.clone() }
+    }
+}
+
+impl std::fmt::Display for CallContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.method)?;
+        if let Some(target) = &self.target {
+            write!(f, " on {}", target)?;
+        }
+        if let Some(column) = self.column {
+            write!(f, ", column {}", column)?;
+        }
+        Ok(())
+    }
+}
+
+/// A broad classification of an [`Error`], as returned by [`Error::kind`]
+///
+/// [`Error`]: crate::Error
+/// [`Error::kind`]: crate::Error::kind
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    /// The server could not be reached, or the request or response was lost or garbled in
+    /// transit.
+    Transport,
+    /// The server rejected a call (an XMLRPC fault) for a reason not covered by a more specific
+    /// kind.
+    Fault,
+    /// The requested item (e.g., a download) does not exist on the server.
+    NotFound,
+    /// The server does not support the requested method.
+    Unsupported,
+    /// A response did not have the expected structure or type.
+    UnexpectedStructure,
+    /// An argument or local input was invalid.
+    InvalidInput,
+    /// An operation did not complete within the allotted time.
+    Timeout,
+    /// A local I/O operation failed.
+    Io,
+    /// A download's data is incomplete.
+    Incomplete,
+}
+
+// xmlrpc-c's fault code for calls of undefined methods.
+const FAULT_NO_SUCH_METHOD: i32 = -506;
+
+fn fault_kind(fault: &xmlrpc::Fault) -> ErrorKind {
+    if fault.fault_code == FAULT_NO_SUCH_METHOD {
+        ErrorKind::Unsupported
+    } else if fault.fault_string.contains("Could not find info-hash") {
+        // rtorrent's response to item commands for a download that is not loaded.
+        ErrorKind::NotFound
+    } else {
+        ErrorKind::Fault
+    }
+}
+
+impl Error {
+    /// Classify this error.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::XmlRpc(xe) => match xe.fault() {
+                Some(fault) => fault_kind(fault),
+                None => ErrorKind::Transport,
+            },
+            Error::Fault(fault) => fault_kind(fault),
+            Error::UnexpectedStructure(_) => ErrorKind::UnexpectedStructure,
+            Error::Io(_) => ErrorKind::Io,
+            Error::InvalidTorrent(_) | Error::UnsafePath(_) | Error::InvalidInfohash(_) => {
+                ErrorKind::InvalidInput
+            }
+            Error::Timeout(_) => ErrorKind::Timeout,
+            Error::DownloadNotFound(_) => ErrorKind::NotFound,
+            Error::UnsupportedMethod(_) => ErrorKind::Unsupported,
+            Error::Incomplete(_) => ErrorKind::Incomplete,
+            Error::Call(_, inner) => inner.kind(),
+        }
+    }
+
+    /// Get the context of the XMLRPC call this error occurred in, if known.
+    pub fn context(&self) -> Option<&CallContext> {
+        match self {
+            Error::Call(context, _) => Some(context),
+            _ => None,
+        }
+    }
+
+    /// Get the underlying error, without any call context.
+    pub fn without_context(&self) -> &Error {
+        match self {
+            Error::Call(_, inner) => inner.without_context(),
+            other => other,
+        }
+    }
+
+    // Attach `context` to this error, unless it already has some (from a more specific call).
+    pub(crate) fn in_call(self, context: CallContext) -> Self {
+        match self {
+            Error::Call(..) => self,
+            other => Error::Call(context, Box::new(other)),
+        }
+    }
+
+    /// Did the error occur because the requested item (e.g., a download that was just erased)
+    /// does not exist?
+    pub fn is_not_found(&self) -> bool {
+        self.kind() == ErrorKind::NotFound
+    }
+
+    /// Did the error occur communicating with the server (e.g., rtorrent is down)?
+    pub fn is_transport(&self) -> bool {
+        self.kind() == ErrorKind::Transport
+    }
+
+    /// Might the same request succeed if retried later?
+    pub fn is_retryable(&self) -> bool {
+        matches!(self.kind(), ErrorKind::Transport | ErrorKind::Timeout)
+    }
 }
 
 impl From<xmlrpc::Error> for Error {
@@ -89,6 +316,12 @@ impl From<xmlrpc::Error> for Error {
     }
 }
 
+impl From<std::io::Error> for Error {
+    fn from(x: std::io::Error) -> Self {
+        Error::Io(x)
+    }
+}
+
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
@@ -98,6 +331,36 @@ impl std::fmt::Display for Error {
             Error::UnexpectedStructure(us) => {
                 write!(f, "Unexpected XML structure: {}", us)
             }
+            Error::Fault(fault) => {
+                write!(f, "XML-RPC fault: {}", fault)
+            }
+            Error::Io(ioe) => {
+                write!(f, "I/O: {}", ioe)
+            }
+            Error::InvalidTorrent(it) => {
+                write!(f, "Invalid torrent metainfo: {}", it)
+            }
+            Error::Timeout(to) => {
+                write!(f, "Timed out: {}", to)
+            }
+            Error::UnsafePath(up) => {
+                write!(f, "Refusing to operate on unsafe path: {}", up)
+            }
+            Error::InvalidInfohash(ih) => {
+                write!(f, "Invalid infohash: {}", ih)
+            }
+            Error::DownloadNotFound(hash) => {
+                write!(f, "Download not found: {}", hash)
+            }
+            Error::UnsupportedMethod(um) => {
+                write!(f, "Unsupported method: {}", um)
+            }
+            Error::Incomplete(inc) => {
+                write!(f, "Incomplete download: {}", inc)
+            }
+            Error::Call(context, inner) => {
+                write!(f, "In {}: {}", context, inner)
+            }
         }
     }
 }
@@ -106,25 +369,81 @@ impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             Error::XmlRpc(xe) => Some(xe),
+            Error::Fault(fault) => Some(fault),
+            Error::Io(ioe) => Some(ioe),
+            Error::Call(_, inner) => Some(inner.as_ref()),
             _ => None,
         }
     }
 }
 
+// Call `method` with `args` and convert the result to `T`.  Errors are annotated with the method
+// and its target (the first argument).
+pub(crate) fn call_method<T: TryFromValue>(endpoint: &str, method: &str, args: Vec<Value>)
+    -> Result<T> {
+    let mut req = Request::new(method);
+    for arg in &args {
+        req = req.arg(arg.clone());
+    }
+    req.call_url(endpoint)
+        .map_err(Error::from)
+        .and_then(|val| T::try_from_value(&val))
+        .map_err(|e| e.in_call(CallContext::new(method, args.first())))
+}
+
+// Quote `s` as a single argument of an rtorrent command (e.g., the value in "d.custom1.set=..."),
+// so that commas and other special characters are passed through verbatim.
+pub(crate) fn quote_command_arg(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
 macro_rules! server_getter {
     ($(#[$meta:meta])* $method: ident, $api: literal, $ty: ty) => {
         $(#[$meta])*
         pub fn $method(&self) -> Result<$ty> {
-            let val = Request::new($api)
-                .call_url(self.endpoint())?;
-            <$ty as TryFromValue>::try_from_value(&val)
+            call_method(self.endpoint(), $api, vec![])
+        }
+    }
+}
+
+macro_rules! server_setter {
+    ($(#[$meta:meta])* $method: ident, $api: literal, $ty: ty) => {
+        $(#[$meta])*
+        pub fn $method(&self, new: $ty) -> Result<()> {
+            call_method(self.endpoint(), $api, vec![Value::from(""), Value::from(new)])
         }
     }
 }
 
+/// How [`Server::pause_all`] pauses an instance
+///
+/// [`Server::pause_all`]: crate::Server::pause_all
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PauseMode {
+    /// Limit the global download and upload rates to the minimum (1 KiB/s).  Downloads stay
+    /// started and connected to their peers and trackers.
+    Throttle,
+    /// Stop every started download.
+    Stop,
+}
+
+// What `Server::resume_all` restores.
+#[derive(Debug)]
+enum Paused {
+    // The prior global (down, up) rate limits.
+    Throttle(i64, i64),
+    // The infohashes of the downloads that were started.
+    Stop(Vec<String>),
+}
+
 #[derive(Debug)]
 struct ServerInner {
     endpoint: String,
+    // Cached result of system.listMethods.
+    methods: Mutex<Option<Arc<BTreeSet<String>>>>,
+    // Set by `pause_all`, until `resume_all`.
+    paused: Mutex<Option<Paused>>,
 }
 
 /// `Server` represents a logical rtorrent instance
@@ -146,71 +465,522 @@ impl Server {
     /// # Ok::<(), rtorrent::Error>(())
     /// ```
     pub fn new(endpoint: &str) -> Self {
-        Self { inner: Arc::new(ServerInner { endpoint: endpoint.to_owned() }) }
+        Self {
+            inner: Arc::new(ServerInner {
+                endpoint: endpoint.to_owned(),
+                methods: Mutex::new(None),
+                paused: Mutex::new(None),
+            })
+        }
     }
 
     #[inline]
-    fn endpoint(&self) -> &str {
+    pub(crate) fn endpoint(&self) -> &str {
         &self.inner.endpoint
     }
 
+    /// Call an arbitrary XMLRPC `method` with `args`, converting the result to `T`.
+    ///
+    /// This is an escape hatch for rtorrent APIs not otherwise wrapped by this crate.  Note that
+    /// most rtorrent commands take a target as their first argument: the empty string (`""`)
+    /// for global commands, or the item (e.g., a [`Download`]) for item commands.
+    ///
+    /// ```no_run
+    /// # use rtorrent_xmlrpc_bindings as rtorrent;
+    /// let server = rtorrent::Server::new("http://1.2.3.4/RPC2");
+    /// let dl = &server.download_list()?[0];
+    /// let size = server.call::<i64>("d.size_bytes", &[dl])?;
+    /// server.call::<()>("d.custom.set", &[dl, &"mykey", &"myvalue"])?;
+    /// # Ok::<(), rtorrent::Error>(())
+    /// ```
+    pub fn call<T: TryFromValue>(&self, method: &str, args: &[&dyn ToValue]) -> Result<T> {
+        call_method(self.endpoint(), method, args.iter().map(|arg| arg.to_value()).collect())
+    }
+
+    /// Get the set of XMLRPC methods supported by this instance (`system.listMethods`).
+    ///
+    /// The set is fetched once and cached for the lifetime of this `Server` (and its clones);
+    /// use [`clear_supported_methods`] to fetch it again, e.g. after rtorrent is upgraded.
+    ///
+    /// [`clear_supported_methods`]: crate::Server::clear_supported_methods
+    pub fn supported_methods(&self) -> Result<Arc<BTreeSet<String>>> {
+        if let Some(methods) = &*self.inner.methods.lock().unwrap() {
+            return Ok(methods.clone());
+        }
+        let methods = Arc::new(call_method::<Vec<String>>(self.endpoint(), "system.listMethods",
+                                                          vec![])?
+                               .into_iter()
+                               .collect::<BTreeSet<_>>());
+        *self.inner.methods.lock().unwrap() = Some(methods.clone());
+        Ok(methods)
+    }
+
+    /// Forget the cached result of [`supported_methods`].
+    ///
+    /// [`supported_methods`]: crate::Server::supported_methods
+    pub fn clear_supported_methods(&self) {
+        *self.inner.methods.lock().unwrap() = None;
+    }
+
     /// Get a list of all downloads loaded in this instance of rtorrent.
     pub fn download_list(&self) -> Result<Vec<Download>> {
-        let raw_list = Request::new("download_list").call_url(self.endpoint())?;
+        let raw_list: Value = call_method(self.endpoint(), "download_list", vec![])?;
         value_conversion::list(&raw_list)?
             .iter()
-            .map(|v| Download::from_value(&self, v))
+            .map(|v| Download::from_value(self, v))
             .collect()
     }
 
+    /// Get a [`DownloadSummary`] of every download in `view` (e.g., `"main"`), in a single round
+    /// trip.
+    ///
+    /// [`DownloadSummary`]: crate::DownloadSummary
+    pub fn download_summaries(&self, view: &str) -> Result<Vec<(Download, DownloadSummary)>> {
+        DownloadSummary::fetch(self, multicall::d::MultiBuilder::new(self, view))
+    }
+
+    /// Group every download by the host of its trackers (e.g., `"tracker.example.org"`).
+    ///
+    /// A download with trackers on several hosts appears under each of them; downloads without
+    /// trackers (e.g., DHT-only magnets) are not included.  The downloads and their tracker URLs
+    /// are fetched in two round trips, regardless of the number of downloads.
+    ///
+    /// ```no_run
+    /// # use rtorrent_xmlrpc_bindings as rtorrent;
+    /// let server = rtorrent::Server::new("http://1.2.3.4/RPC2");
+    /// for (host, downloads) in server.downloads_by_tracker()? {
+    ///     println!("{}: {} downloads", host, downloads.len());
+    /// }
+    /// # Ok::<(), rtorrent::Error>(())
+    /// ```
+    pub fn downloads_by_tracker(&self) -> Result<BTreeMap<String, Vec<Download>>> {
+        let downloads = self.download_list()?;
+        let urls = tracker::tracker_urls(self, &downloads)?;
+        let mut by_host = BTreeMap::<String, Vec<Download>>::new();
+        for (download, urls) in downloads.into_iter().zip(urls) {
+            let hosts = urls.iter().map(|url| tracker::url_host(url)).collect::<BTreeSet<_>>();
+            for host in hosts.into_iter().filter(|host| !host.is_empty()) {
+                by_host.entry(host).or_default().push(download.clone());
+            }
+        }
+        Ok(by_host)
+    }
+
+    /// Announce every started download with a tracker at `domain` (or a subdomain of it) to its
+    /// trackers (see [`Download::tracker_announce`]), e.g. to recover after a tracker outage.
+    ///
+    /// If `delay` is zero, all announces are sent in a single round trip; otherwise, they are
+    /// sent one at a time, `delay` apart, to spare the tracker a burst of requests.  Returns the
+    /// announced downloads.
+    ///
+    /// [`Download::tracker_announce`]: crate::Download::tracker_announce
+    pub fn reannounce_tracker(&self, domain: &str, delay: Duration) -> Result<Vec<Download>> {
+        let started = multicall::d::MultiBuilder::new(self, "started")
+            .dynamic()
+            .call("d.hash")
+            .invoke()?
+            .iter()
+            .map(|row| Download::from_value(self, &row[0]))
+            .collect::<Result<Vec<_>>>()?;
+        let urls = tracker::tracker_urls(self, &started)?;
+        let matching = started.into_iter()
+            .zip(urls)
+            .filter(|(_, urls)| {
+                urls.iter().any(|url| tracker::host_matches(&tracker::url_host(url), domain))
+            })
+            .map(|(download, _)| download)
+            .collect::<Vec<_>>();
+
+        if delay.is_zero() {
+            if !matching.is_empty() {
+                let mut mc = multicall::system::SystemMultiCall::new();
+                for download in &matching {
+                    mc.push("d.tracker_announce", vec![Value::from(download)]);
+                }
+                mc.invoke(self)?;
+            }
+        } else {
+            for (i, download) in matching.iter().enumerate() {
+                if i > 0 {
+                    std::thread::sleep(delay);
+                }
+                download.tracker_announce()?;
+            }
+        }
+        Ok(matching)
+    }
+
+    /// Start every download in `view` (e.g., `"stopped"`), in a single round trip.
+    ///
+    /// Returns the downloads in the view.
+    pub fn start_view(&self, view: &str) -> Result<Vec<Download>> {
+        self.view_command(view, "d.start")
+    }
+
+    /// Stop every download in `view` (e.g., `"seeding"`), in a single round trip.
+    ///
+    /// Returns the downloads in the view.
+    pub fn stop_view(&self, view: &str) -> Result<Vec<Download>> {
+        self.view_command(view, "d.stop")
+    }
+
+    /// Pause all transfers on this instance, as described by `mode`, remembering the prior state
+    /// for [`resume_all`].
+    ///
+    /// The state is remembered by this `Server` (and its clones), not by rtorrent.  Pausing an
+    /// instance that is already paused has no effect.  If throttling fails after the prior limits
+    /// were read, the instance is still considered paused, so that [`resume_all`] restores them.
+    ///
+    /// ```no_run
+    /// # use rtorrent_xmlrpc_bindings as rtorrent;
+    /// use rtorrent::PauseMode;
+    ///
+    /// let server = rtorrent::Server::new("http://1.2.3.4/RPC2");
+    /// server.pause_all(PauseMode::Stop)?;
+    /// // ...
+    /// let resumed = server.resume_all()?;
+    /// println!("Restarted {} downloads", resumed.len());
+    /// # Ok::<(), rtorrent::Error>(())
+    /// ```
+    ///
+    /// [`resume_all`]: crate::Server::resume_all
+    pub fn pause_all(&self, mode: PauseMode) -> Result<()> {
+        const MIN_RATE: i64 = 1024;
+
+        let mut paused = self.inner.paused.lock().unwrap();
+        if paused.is_some() {
+            return Ok(());
+        }
+        match mode {
+            PauseMode::Throttle => {
+                let (down, up) = (self.down_max_rate()?, self.up_max_rate()?);
+                // Remember the prior limits first, so that they can be restored even if setting
+                // the new ones fails partway.
+                *paused = Some(Paused::Throttle(down, up));
+                self.set_down_max_rate(MIN_RATE)?;
+                self.set_up_max_rate(MIN_RATE)?;
+            }
+            PauseMode::Stop => {
+                let stopped = self.stop_view("started")?;
+                *paused = Some(Paused::Stop(stopped.iter()
+                                            .map(|dl| dl.sha1_hex().to_owned())
+                                            .collect()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Undo [`pause_all`]: restore the prior global rate limits, or restart exactly the downloads
+    /// that were started (and still exist).
+    ///
+    /// Returns the restarted downloads (none, if the instance was throttled or not paused).
+    ///
+    /// [`pause_all`]: crate::Server::pause_all
+    pub fn resume_all(&self) -> Result<Vec<Download>> {
+        let mut paused = self.inner.paused.lock().unwrap();
+        let resumed = match &*paused {
+            None => Vec::new(),
+            Some(Paused::Throttle(down, up)) => {
+                self.set_down_max_rate(*down)?;
+                self.set_up_max_rate(*up)?;
+                Vec::new()
+            }
+            Some(Paused::Stop(hashes)) => {
+                // Skip downloads erased while paused.
+                let existing = self.download_list()?
+                    .into_iter()
+                    .map(|dl| dl.sha1_hex().to_owned())
+                    .collect::<BTreeSet<_>>();
+                let hashes = hashes.iter()
+                    .filter(|hash| existing.contains(*hash))
+                    .map(String::as_str)
+                    .collect::<Vec<_>>();
+                if !hashes.is_empty() {
+                    multicall::d::MultiBuilder::for_hashes(self, &hashes)
+                        .dynamic()
+                        .call("d.start")
+                        .invoke()?;
+                }
+                hashes.iter().map(|hash| Download::from_hash(self, hash)).collect()
+            }
+        };
+        *paused = None;
+        Ok(resumed)
+    }
+
+    /// Is this instance paused by [`pause_all`]?
+    ///
+    /// [`pause_all`]: crate::Server::pause_all
+    pub fn is_paused(&self) -> bool {
+        self.inner.paused.lock().unwrap().is_some()
+    }
+
+    // Run the `d.*` command `method` against every download in `view`, in a single d.multicall2.
+    fn view_command(&self, view: &str, method: &str) -> Result<Vec<Download>> {
+        multicall::d::MultiBuilder::new(self, view)
+            .dynamic()
+            .call("d.hash")
+            .call(method)
+            .invoke()?
+            .iter()
+            .map(|row| Download::from_value(self, &row[0]))
+            .collect()
+    }
+
+    /// Get a snapshot of global transfer and resource statistics for this instance.
+    ///
+    /// All values are gathered in a single round trip.
+    pub fn stats(&self) -> Result<Stats> {
+        Stats::fetch(self)
+    }
+
+    /// Get the disk usage of the downloads in `view` (e.g., `"main"`), grouped as specified by
+    /// `group_by`.
+    ///
+    /// The report is computed from a single multicall (plus a second, to fetch tracker URLs, when
+    /// grouping by tracker).
+    pub fn disk_usage(&self, view: &str, group_by: GroupBy) -> Result<DiskUsageReport> {
+        DiskUsageReport::fetch(self, view, group_by)
+    }
+
+    /// Get the health of the trackers of the downloads in `view` (e.g., `"main"`), grouped by
+    /// tracker host.
+    ///
+    /// The report is computed from two multicalls, regardless of the number of downloads.
+    pub fn tracker_health(&self, view: &str) -> Result<TrackerHealthReport> {
+        TrackerHealthReport::fetch(self, view)
+    }
+
+    /// Get a handle to the existing ratio group `name` (e.g., the built-in `"seeding"` group).
+    ///
+    /// This does not validate that the group exists.
+    pub fn ratio_group(&self, name: &str) -> RatioGroup {
+        RatioGroup::new(self, name)
+    }
+
+    /// Create a new ratio group `name`, applying to the downloads in `view`.
+    pub fn insert_ratio_group(&self, name: &str, view: &str) -> Result<RatioGroup> {
+        call_method::<()>(self.endpoint(), "group.insert",
+                          vec![Value::from(""), Value::from(name), Value::from(view)])?;
+        Ok(RatioGroup::new(self, name))
+    }
+
     /// Add torrent from url/magnetlink.
     ///
     /// If start is true, also start the added download.
-    pub fn load_torrent_url(&self, link: &str, start: bool) -> Result<i64> {
+    ///
+    /// For magnet links, the infohash is known in advance and a [`Download`] handle is returned.
+    /// Note that rtorrent adds the item asynchronously, so the item may not be visible on the
+    /// server immediately.  For other URLs, the infohash cannot be known until rtorrent has
+    /// fetched the metainfo, and `None` is returned.
+    pub fn load_torrent_url(&self, link: &str, start: bool) -> Result<Option<Download>> {
         let load = if start {
             "load.start_verbose"
         } else {
             "load.verbose"
         };
-        let raw_response = Request::new(load)
-            .arg("")
-            .arg(link.to_string())
-            .call_url(self.endpoint())?;
-        <i64 as TryFromValue>::try_from_value(&raw_response)
+        call_method::<()>(self.endpoint(), load, vec![Value::from(""), Value::from(link)])?;
+        Ok(magnet_infohash_hex(link).map(|hash| Download::from_hash(self, &hash)))
     }
 
     /// Add torrent from torrent file contents.
     ///
     /// If start is true, also start the added download.
-    pub fn load_torrent_bytes(&self, contents: &[u8], start: bool) -> Result<i64> {
+    ///
+    /// The infohash is computed from `contents`, and a [`Download`] handle for the new item is
+    /// returned.
+    pub fn load_torrent_bytes(&self, contents: &[u8], start: bool) -> Result<Download> {
+        let hash = bencode::infohash_hex(contents)?;
         let load = if start {
             "load.raw_start_verbose"
         } else {
             "load.raw_verbose"
         };
-        let raw_response = Request::new(load)
-            .arg("")
-            .arg(contents.to_vec())
-            .call_url(self.endpoint())?;
-        <i64 as TryFromValue>::try_from_value(&raw_response)
+        call_method::<()>(self.endpoint(), load,
+                          vec![Value::from(""), Value::from(contents.to_vec())])?;
+        Ok(Download::from_hash(self, &hash))
+    }
+
+    /// Add torrent from a torrent file on the local filesystem.
+    ///
+    /// The file is read locally and its contents are sent to rtorrent, so `path` need not be
+    /// accessible to the rtorrent host.  If start is true, also start the added download.
+    pub fn load_torrent_file<P: AsRef<Path>>(&self, path: P, start: bool) -> Result<Download> {
+        let contents = std::fs::read(path)?;
+        self.load_torrent_bytes(&contents, start)
+    }
+
+    /// Wait for a download added with one of the `load_torrent_*` methods to appear on the server.
+    ///
+    /// rtorrent loads items asynchronously (for magnet links and URLs, potentially after a
+    /// network fetch).  This method polls the server every `poll_interval` until `download` exists
+    /// and returns it, or returns [`Error::Timeout`] once `timeout` has elapsed.
+    ///
+    /// ```no_run
+    /// # use rtorrent_xmlrpc_bindings as rtorrent;
+    /// use std::time::Duration;
+    ///
+    /// let server = rtorrent::Server::new("http://1.2.3.4/RPC2");
+    /// let magnet = "magnet:?xt=urn:btih:0123456789ABCDEF0123456789ABCDEF01234567";
+    /// if let Some(dl) = server.load_torrent_url(magnet, true)? {
+    ///     let dl = server.await_load(dl, Duration::from_millis(250), Duration::from_secs(10))?;
+    ///     println!("Loaded: {}", dl.name()?);
+    /// }
+    /// # Ok::<(), rtorrent::Error>(())
+    /// ```
+    ///
+    /// [`Error::Timeout`]: crate::Error::Timeout
+    pub fn await_load(&self, download: Download, poll_interval: Duration, timeout: Duration)
+        -> Result<Download> {
+        let start = Instant::now();
+        loop {
+            if download.exists()? {
+                return Ok(download);
+            }
+            if start.elapsed() >= timeout {
+                return Err(Error::Timeout(
+                    format!("waiting for download {} to load", download.sha1_hex())
+                ));
+            }
+            std::thread::sleep(poll_interval);
+        }
+    }
+
+    /// Exit rtorrent gracefully (see [`exit_rtorrent`]), escalating to an immediate shutdown (see
+    /// [`exit_rtorrent_quick`]) if the instance is still responding after `timeout`.
+    ///
+    /// [`exit_rtorrent`]: crate::Server::exit_rtorrent
+    /// [`exit_rtorrent_quick`]: crate::Server::exit_rtorrent_quick
+    pub fn exit_rtorrent_timeout(&self, timeout: Duration) -> Result<()> {
+        const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+        self.exit_rtorrent()?;
+        let start = Instant::now();
+        while start.elapsed() < timeout {
+            match self.pid() {
+                Ok(_) => {}
+                // The instance has stopped responding.
+                Err(e) if e.is_transport() => return Ok(()),
+                Err(e) => return Err(e),
+            }
+            std::thread::sleep(POLL_INTERVAL.min(timeout.saturating_sub(start.elapsed())));
+        }
+        match self.exit_rtorrent_quick() {
+            // The instance may have finished exiting in the meantime.
+            Err(e) if e.is_transport() => Ok(()),
+            res => res.map(|_| ()),
+        }
+    }
+
+    /// Run `program` with `args` on the rtorrent host (`execute.throw`), failing if it exits
+    /// unsuccessfully.
+    ///
+    /// Arguments are passed directly to the program, without interpretation by a shell.
+    pub fn execute(&self, program: &str, args: &[&str]) -> Result<()> {
+        let mut call_args = vec![Value::from(""), Value::from(program)];
+        call_args.extend(args.iter().map(|arg| Value::from(*arg)));
+        call_method(self.endpoint(), "execute.throw", call_args)
+    }
+
+    /// Run `program` with `args` on the rtorrent host (`execute.capture`), returning its standard
+    /// output.
+    ///
+    /// Arguments are passed directly to the program, without interpretation by a shell.
+    pub fn execute_capture(&self, program: &str, args: &[&str]) -> Result<String> {
+        let mut call_args = vec![Value::from(""), Value::from(program)];
+        call_args.extend(args.iter().map(|arg| Value::from(*arg)));
+        call_method(self.endpoint(), "execute.capture", call_args)
+    }
+
+    /// Schedule `command` (in rtorrent's command syntax, e.g. `"session.save="`) to run on the
+    /// rtorrent host (`schedule2`), replacing any existing schedule named `name`.
+    ///
+    /// `start` and `interval` are either a number of seconds (e.g., `"60"`), or a time of day
+    /// (`"hh:mm:ss"`, in the rtorrent host's time zone) and period (e.g., `"24:00:00"`).  An
+    /// `interval` of `"0"` runs the command only once.
+    pub fn schedule(&self, name: &str, start: &str, interval: &str, command: &str) -> Result<()> {
+        call_method(self.endpoint(), "schedule2",
+                    vec![Value::from(""), Value::from(name), Value::from(start),
+                         Value::from(interval), Value::from(command)])
+    }
+
+    /// Remove the schedule named `name` (`schedule_remove2`).  See [`schedule`].
+    ///
+    /// [`schedule`]: crate::Server::schedule
+    pub fn schedule_remove(&self, name: &str) -> Result<()> {
+        call_method(self.endpoint(), "schedule_remove2", vec![Value::from(""), Value::from(name)])
+    }
+
+    /// Open a log output named `name`, writing to the file at `path` on the rtorrent host.
+    ///
+    /// Log events are not written to the new output until some event group is attached to it with
+    /// [`log_add_output`].
+    ///
+    /// [`log_add_output`]: crate::Server::log_add_output
+    pub fn log_open_file(&self, name: &str, path: &str) -> Result<()> {
+        call_method(self.endpoint(), "log.open_file",
+                    vec![Value::from(""), Value::from(name), Value::from(path)])
+    }
+
+    /// Attach the log event group `group` (e.g., `"tracker_debug"`, `"rpc_events"`, `"info"`) to
+    /// the previously opened log output `name`.
+    pub fn log_add_output(&self, group: &str, name: &str) -> Result<()> {
+        call_method(self.endpoint(), "log.add_output",
+                    vec![Value::from(""), Value::from(group), Value::from(name)])
+    }
+
+    /// Close the log output `name`, detaching any event groups attached to it.
+    pub fn log_close(&self, name: &str) -> Result<()> {
+        call_method(self.endpoint(), "log.close", vec![Value::from(""), Value::from(name)])
+    }
+
+    /// Set the path of the file which receives rtorrent's console messages log.
+    pub fn set_log_messages(&self, path: &str) -> Result<()> {
+        call_method(self.endpoint(), "log.messages.set", vec![Value::from(""), Value::from(path)])
     }
 
     server_getter!(
         /// Get the IP address associated with this rtorrent instance.
         ip, "network.bind_address", String);
+    server_getter!(
+        /// Get the IP address this rtorrent instance binds to, parsed.
+        bind_address, "network.bind_address", IpAddr);
     server_getter!(
         /// Get the port(s) associated with this rtorrent instance.
         port, "network.port_range", String);
+    server_getter!(
+        /// Get DHT status and statistics, as a struct keyed by statistic name (e.g., `"nodes"`,
+        /// `"peers"`, `"bytes_read"`).  The set of keys depends on whether DHT is active.
+        dht_statistics, "dht.statistics", BTreeMap<String, Value>);
     server_getter!(
         /// Get the hostname associated with this rtorrent instance.
         hostname, "system.hostname", String);
     server_getter!(
         /// Get the time in seconds since Unix Epoch when this rtorrent instance was started.
         startup_time, "system.startup_time", i64);
+    server_getter!(
+        /// Get the process ID of this rtorrent instance.
+        pid, "system.pid", i64);
+    server_getter!(
+        /// Get the current time in seconds since Unix Epoch, according to this rtorrent instance.
+        time_seconds, "system.time_seconds", i64);
+    server_getter!(
+        /// Get the current working directory of this rtorrent instance.
+        cwd, "system.cwd", String);
+    server_getter!(
+        /// Get the directory new downloads store their data in, unless otherwise specified.
+        default_directory, "directory.default", String);
+    server_getter!(
+        /// Get the umask used by this rtorrent instance when creating files.
+        umask, "system.umask", i64);
     server_getter!(
         /// Exit rtorrent, informing trackers that we are going away and waiting some time for them
         /// to acknowledge.
         exit_rtorrent, "system.shutdown.normal", i64);
+    server_getter!(
+        /// Exit rtorrent immediately, without waiting for trackers to acknowledge that we are
+        /// going away.
+        exit_rtorrent_quick, "system.shutdown.quick", i64);
     server_getter!(
         /// Get the XMLRPC API version associated with this instance.
         api_version, "system.api_version", String);
@@ -233,6 +1003,116 @@ impl Server {
     server_getter!(
         /// Get the current upload rate for this instance (bytes/s).
         up_rate, "throttle.global_up.rate", i64);
+
+    server_getter!(
+        /// Get the global download rate limit (bytes/s).  Zero is unlimited.
+        down_max_rate, "throttle.global_down.max_rate", i64);
+    server_setter!(
+        /// Set the global download rate limit (bytes/s).  Zero is unlimited.
+        set_down_max_rate, "throttle.global_down.max_rate.set", i64);
+    server_getter!(
+        /// Get the global upload rate limit (bytes/s).  Zero is unlimited.
+        up_max_rate, "throttle.global_up.max_rate", i64);
+    server_setter!(
+        /// Set the global upload rate limit (bytes/s).  Zero is unlimited.
+        set_up_max_rate, "throttle.global_up.max_rate.set", i64);
+
+    server_getter!(
+        /// Get the socket receive buffer size (bytes).  Zero uses the operating system default.
+        receive_buffer_size, "network.receive_buffer.size", i64);
+    server_setter!(
+        /// Set the socket receive buffer size (bytes).
+        set_receive_buffer_size, "network.receive_buffer.size.set", i64);
+    server_getter!(
+        /// Get the socket send buffer size (bytes).  Zero uses the operating system default.
+        send_buffer_size, "network.send_buffer.size", i64);
+    server_setter!(
+        /// Set the socket send buffer size (bytes).
+        set_send_buffer_size, "network.send_buffer.size.set", i64);
+    server_getter!(
+        /// Get the DNS cache timeout used for HTTP requests (seconds).
+        http_dns_cache_timeout, "network.http.dns_cache_timeout", i64);
+    server_setter!(
+        /// Set the DNS cache timeout used for HTTP requests (seconds).
+        set_http_dns_cache_timeout, "network.http.dns_cache_timeout.set", i64);
+    server_getter!(
+        /// Get the maximum number of sockets rtorrent may open.
+        max_open_sockets, "network.max_open_sockets", i64);
+    server_setter!(
+        /// Set the maximum number of sockets rtorrent may open.
+        set_max_open_sockets, "network.max_open_sockets.set", i64);
+    server_getter!(
+        /// Get the maximum number of files rtorrent may open.
+        max_open_files, "network.max_open_files", i64);
+    server_setter!(
+        /// Set the maximum number of files rtorrent may open.
+        set_max_open_files, "network.max_open_files.set", i64);
+
+    server_getter!(
+        /// Get the amount of memory currently used by rtorrent's piece cache (bytes).
+        pieces_memory_current, "pieces.memory.current", i64);
+    server_getter!(
+        /// Get the maximum amount of memory rtorrent may use for its piece cache (bytes).
+        pieces_memory_max, "pieces.memory.max", i64);
+    server_setter!(
+        /// Set the maximum amount of memory rtorrent may use for its piece cache (bytes).
+        set_pieces_memory_max, "pieces.memory.max.set", i64);
+    server_getter!(
+        /// Are downloads hash checked again once they complete?
+        pieces_hash_on_completion, "pieces.hash.on_completion", bool);
+    server_setter!(
+        /// Control whether downloads are hash checked again once they complete.
+        set_pieces_hash_on_completion, "pieces.hash.on_completion.set", bool);
+    server_getter!(
+        /// Is chunk data always synced to disk safely (rather than only when disk space is low)?
+        pieces_sync_always_safe, "pieces.sync.always_safe", bool);
+    server_setter!(
+        /// Control whether chunk data is always synced to disk safely.
+        set_pieces_sync_always_safe, "pieces.sync.always_safe.set", bool);
+    server_getter!(
+        /// Get the method used to preload chunks before sending them to peers.  Possible values
+        /// are: 0, off; 1, madvise; and 2, direct paging.
+        pieces_preload_type, "pieces.preload.type", i64);
+    server_setter!(
+        /// Set the method used to preload chunks.  See [`Server::pieces_preload_type`].
+        set_pieces_preload_type, "pieces.preload.type.set", i64);
+    server_getter!(
+        /// Get the number of chunks that were preloaded before being sent to peers.
+        pieces_stats_preloaded, "pieces.stats_preloaded", i64);
+    server_getter!(
+        /// Get the number of chunks that were sent to peers without being preloaded.
+        pieces_stats_not_preloaded, "pieces.stats_not_preloaded", i64);
+}
+
+// Extract the infohash from a magnet link's "xt=urn:btih:" parameter, as uppercase hex.  Both
+// the hex and base32 encodings are accepted.
+fn magnet_infohash_hex(link: &str) -> Option<String> {
+    let query = link.strip_prefix("magnet:?")?;
+    let btih = query.split('&')
+        .find_map(|param| param.strip_prefix("xt=urn:btih:"))?;
+    match btih.len() {
+        40 if btih.chars().all(|c| c.is_ascii_hexdigit()) => Some(btih.to_ascii_uppercase()),
+        32 => {
+            let mut bits: u64 = 0;
+            let mut nbits = 0;
+            let mut hex = String::with_capacity(40);
+            for c in btih.chars() {
+                let v = match c.to_ascii_uppercase() {
+                    c @ 'A'..='Z' => c as u64 - 'A' as u64,
+                    c @ '2'..='7' => c as u64 - '2' as u64 + 26,
+                    _ => return None,
+                };
+                bits = (bits << 5) | v;
+                nbits += 5;
+                if nbits >= 8 {
+                    nbits -= 8;
+                    hex.push_str(&format!("{:02X}", (bits >> nbits) & 0xff));
+                }
+            }
+            Some(hex)
+        }
+        _ => None,
+    }
 }
 
 unsafe impl Send for Server {}
@@ -248,10 +1128,8 @@ pub(crate) mod macros {
         ) => {
             $(#[$meta])*
             pub fn $method(&self) -> Result<$result> {
-                let val = Request::new(concat!($ns, stringify!($method)))
-                    .arg(self)
-                    .call_url(self.endpoint())?;
-                <$result as TryFromValue>::try_from_value(&val)
+                $crate::call_method(self.endpoint(), concat!($ns, stringify!($method)),
+                                    vec![$crate::Value::from(self)])
             }
         }
     }
@@ -263,10 +1141,8 @@ pub(crate) mod macros {
         ) => {
             $(#[$meta])*
             pub fn $method(&self) -> Result<$result> {
-                let val = Request::new(concat!($ns, $apimethod))
-                    .arg(self)
-                    .call_url(self.endpoint())?;
-                <$result as TryFromValue>::try_from_value(&val)
+                $crate::call_method(self.endpoint(), concat!($ns, $apimethod),
+                                    vec![$crate::Value::from(self)])
             }
         }
     }
@@ -279,13 +1155,47 @@ pub(crate) mod macros {
         ) => {
             $(#[$meta])*
             pub fn $rmethod(&self, new: $ty) -> Result<()> {
-                let val = Request::new(concat!($ns, stringify!($apimethod), ".set"))
-                    .arg(self)
-                    .arg(new)
-                    .call_url(self.endpoint())?;
-                <() as TryFromValue>::try_from_value(&val)
+                $crate::call_method(self.endpoint(),
+                                    concat!($ns, stringify!($apimethod), ".set"),
+                                    vec![$crate::Value::from(self), $crate::Value::from(new)])
             }
         }
     }
     pub(crate) use prim_setter;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{magnet_infohash_hex, quote_command_arg};
+
+    #[test]
+    fn magnet_infohash() {
+        let hex = "0123456789ABCDEF0123456789ABCDEF01234567";
+        assert_eq!(magnet_infohash_hex(&format!("magnet:?xt=urn:btih:{}", hex)).unwrap(), hex);
+        assert_eq!(magnet_infohash_hex(&format!("magnet:?xt=urn:btih:{}&dn=x",
+                                                hex.to_ascii_lowercase())).unwrap(), hex);
+        // The same hash in base32 (RFC 4648), in either case.
+        let base32 = "AERUKZ4JVPG66AJDIVTYTK6N54ASGRLH";
+        assert_eq!(magnet_infohash_hex(&format!("magnet:?dn=x&xt=urn:btih:{}", base32)).unwrap(),
+                   hex);
+        assert_eq!(magnet_infohash_hex(&format!("magnet:?xt=urn:btih:{}",
+                                                base32.to_ascii_lowercase())).unwrap(), hex);
+    }
+
+    #[test]
+    fn magnet_infohash_invalid() {
+        for link in &["magnet:?dn=x", "http://example.org/", "magnet:?xt=urn:btih:0123",
+                      "magnet:?xt=urn:btih:0123456789ABCDEF0123456789ABCDEF0123456Z",
+                      "magnet:?xt=urn:btih:AERUKZ4JVPG66AJDIVTYTK6N54ASGRL1"] {
+            assert_eq!(magnet_infohash_hex(link), None, "{}", link);
+        }
+    }
+
+    #[test]
+    fn quote_command_arg_escapes() {
+        assert_eq!(quote_command_arg("a,b c"), r#""a,b c""#);
+        assert_eq!(quote_command_arg(r#"say "hi""#), r#""say \"hi\"""#);
+        assert_eq!(quote_command_arg(r"C:\dir\"), r#""C:\\dir\\""#);
+        assert_eq!(quote_command_arg(""), r#""""#);
+    }
+}
@@ -0,0 +1,104 @@
+/*! Bulk operations on downloads
+
+This module defines the [`TorrentManager`] type and support code.
+
+[`TorrentManager`]: crate::TorrentManager
+!*/
+
+use crate::download::label_encode;
+use crate::multicall::d;
+use crate::{Download, DownloadSummary, Result, Server};
+use xmlrpc::Value;
+
+/// `TorrentManager` applies operations to many downloads at once
+///
+/// Each operation is implemented as one or two multicalls, regardless of the number of downloads
+/// affected, rather than a round trip per download.  Operations return the downloads they were
+/// applied to.
+///
+/// ```no_run
+/// use rtorrent_xmlrpc_bindings as rtorrent;
+///
+/// let my_handle = rtorrent::Server::new("http://1.2.3.4/RPC2");
+/// let manager = rtorrent::TorrentManager::new(&my_handle);
+///
+/// manager.start_all("stopped")?;
+/// manager.set_label_where("main", |_, summary| summary.name.ends_with(".iso"), "linux")?;
+/// let erased = manager.erase_matching("complete", |_, summary| summary.ratio >= 2.0)?;
+/// println!("Erased {} downloads", erased.len());
+/// # Ok::<(), rtorrent::Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct TorrentManager {
+    server: Server,
+}
+
+impl TorrentManager {
+    /// Create a `TorrentManager` for the downloads on `server`.
+    pub fn new(server: &Server) -> Self {
+        Self { server: server.clone() }
+    }
+
+    /// Get the underlying `Server`.
+    pub fn server(&self) -> &Server {
+        &self.server
+    }
+
+    /// Start every download in `view` (see [`Server::start_view`]).
+    ///
+    /// [`Server::start_view`]: crate::Server::start_view
+    pub fn start_all(&self, view: &str) -> Result<Vec<Download>> {
+        self.server.start_view(view)
+    }
+
+    /// Stop every download in `view` (see [`Server::stop_view`]).
+    ///
+    /// [`Server::stop_view`]: crate::Server::stop_view
+    pub fn stop_all(&self, view: &str) -> Result<Vec<Download>> {
+        self.server.stop_view(view)
+    }
+
+    /// Set the ruTorrent-style label (see [`Download::set_label`]) of each download in `view` for
+    /// which `predicate` returns `true`.
+    ///
+    /// [`Download::set_label`]: crate::Download::set_label
+    pub fn set_label_where<F>(&self, view: &str, predicate: F, label: &str)
+        -> Result<Vec<Download>>
+        where F: FnMut(&Download, &DownloadSummary) -> bool {
+        let matching = self.matching(view, predicate)?;
+        if !matching.is_empty() {
+            let mut builder = Self::builder_for(&self.server, &matching).dynamic();
+            builder.push_setter("d.custom1.set", Value::from(label_encode(label)));
+            builder.invoke()?;
+        }
+        Ok(matching)
+    }
+
+    /// Erase each download in `view` for which `predicate` returns `true`.  Downloaded data is
+    /// not deleted.
+    pub fn erase_matching<F>(&self, view: &str, predicate: F) -> Result<Vec<Download>>
+        where F: FnMut(&Download, &DownloadSummary) -> bool {
+        let matching = self.matching(view, predicate)?;
+        if !matching.is_empty() {
+            Self::builder_for(&self.server, &matching)
+                .dynamic()
+                .call("d.erase")
+                .invoke()?;
+        }
+        Ok(matching)
+    }
+
+    fn matching<F>(&self, view: &str, mut predicate: F) -> Result<Vec<Download>>
+        where F: FnMut(&Download, &DownloadSummary) -> bool {
+        Ok(self.server.download_summaries(view)?
+           .into_iter()
+           .filter(|(download, summary)| predicate(download, summary))
+           .map(|(download, _)| download)
+           .collect())
+    }
+
+    fn builder_for(server: &Server, downloads: &[Download]) -> d::MultiBuilder {
+        let hashes = downloads.iter().map(|dl| dl.sha1_hex()).collect::<Vec<_>>();
+        d::MultiBuilder::for_hashes(server, &hashes)
+    }
+}
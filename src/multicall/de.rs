@@ -0,0 +1,278 @@
+//! Deserialization of multicall rows with serde
+
+use crate::{Error, Result};
+use serde::de::{self, DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess,
+                Visitor};
+use serde::forward_to_deserialize_any;
+use xmlrpc::Value;
+
+impl de::Error for Error {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        Error::UnexpectedStructure(msg.to_string())
+    }
+}
+
+/// Deserialize a row of multicall results (`values`, one per entry in `columns`) into `T`.
+///
+/// Structs whose field names all match columns are filled by name; otherwise, structs, tuples,
+/// and sequences are filled by position.
+pub(crate) fn from_row<T: DeserializeOwned>(columns: &[String], values: &[Value]) -> Result<T> {
+    T::deserialize(RowDeserializer { columns, values })
+}
+
+// The field name a column maps onto: the accessor name with its namespace dropped and remaining
+// dots replaced, e.g. "d.down.rate" becomes "down_rate".
+fn field_name(column: &str) -> String {
+    column.split_once('.').map_or(column, |(_, rest)| rest).replace('.', "_")
+}
+
+struct RowDeserializer<'a> {
+    columns: &'a [String],
+    values: &'a [Value],
+}
+
+impl<'de, 'a> de::Deserializer<'de> for RowDeserializer<'a> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_seq(ValueSeq { iter: self.values.iter() })
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_map(RowMap {
+            columns: self.columns.iter(),
+            values: self.values.iter(),
+            value: None,
+        })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(self, _name: &'static str,
+                                          fields: &'static [&'static str], visitor: V)
+        -> Result<V::Value> {
+        // Match fields by the same key `RowMap` presents them under.
+        let by_name = fields.iter().all(|field| {
+            self.columns.iter().any(|column| field_name(column) == *field)
+        });
+        if by_name {
+            self.deserialize_map(visitor)
+        } else {
+            self.deserialize_any(visitor)
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct seq tuple tuple_struct enum identifier
+        ignored_any
+    }
+}
+
+struct RowMap<'a> {
+    columns: std::slice::Iter<'a, String>,
+    values: std::slice::Iter<'a, Value>,
+    value: Option<&'a Value>,
+}
+
+impl<'de, 'a> MapAccess<'de> for RowMap<'a> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        match (self.columns.next(), self.values.next()) {
+            (Some(column), Some(value)) => {
+                self.value = Some(value);
+                seed.deserialize(field_name(column).into_deserializer()).map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        match self.value.take() {
+            Some(value) => seed.deserialize(ValueDeserializer(value)),
+            None => Err(de::Error::custom("value requested before key")),
+        }
+    }
+}
+
+struct ValueSeq<'a> {
+    iter: std::slice::Iter<'a, Value>,
+}
+
+impl<'de, 'a> SeqAccess<'de> for ValueSeq<'a> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        match self.iter.next() {
+            Some(value) => seed.deserialize(ValueDeserializer(value)).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+struct ValueStruct<'a> {
+    iter: std::collections::btree_map::Iter<'a, String, Value>,
+    value: Option<&'a Value>,
+}
+
+impl<'de, 'a> MapAccess<'de> for ValueStruct<'a> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(key.as_str().into_deserializer()).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        match self.value.take() {
+            Some(value) => seed.deserialize(ValueDeserializer(value)),
+            None => Err(de::Error::custom("value requested before key")),
+        }
+    }
+}
+
+struct ValueDeserializer<'a>(&'a Value);
+
+impl<'de, 'a> de::Deserializer<'de> for ValueDeserializer<'a> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.0 {
+            Value::Int(i) => visitor.visit_i32(*i),
+            Value::Int64(i) => visitor.visit_i64(*i),
+            Value::Bool(b) => visitor.visit_bool(*b),
+            Value::String(s) => visitor.visit_str(s),
+            Value::Double(d) => visitor.visit_f64(*d),
+            Value::DateTime(dt) => visitor.visit_string(dt.to_string()),
+            Value::Base64(b) => visitor.visit_bytes(b),
+            Value::Array(a) => visitor.visit_seq(ValueSeq { iter: a.iter() }),
+            Value::Struct(s) => visitor.visit_map(ValueStruct { iter: s.iter(), value: None }),
+            Value::Nil => visitor.visit_unit(),
+        }
+    }
+
+    // rtorrent represents booleans as integers.
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.0 {
+            Value::Int(i) => visitor.visit_bool(*i != 0),
+            Value::Int64(i) => visitor.visit_bool(*i != 0),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.0 {
+            Value::Nil => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V)
+        -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf unit
+        unit_struct seq tuple tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::collections::BTreeMap;
+
+    fn columns(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn positional() {
+        let row = [Value::from("foo"), Value::Int64(42)];
+        let (name, size): (String, i64) =
+            from_row(&columns(&["d.name", "d.size_bytes"]), &row).unwrap();
+        assert_eq!((name.as_str(), size), ("foo", 42));
+
+        // Structs whose fields do not all match columns are filled by position.
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Positional {
+            title: String,
+            bytes: i64,
+        }
+        assert_eq!(from_row::<Positional>(&columns(&["d.name", "d.size_bytes"]), &row).unwrap(),
+                   Positional { title: "foo".to_owned(), bytes: 42 });
+    }
+
+    #[test]
+    fn by_name() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct ByName {
+            down_rate: i64,
+            name: String,
+        }
+        let row = [Value::from("foo"), Value::Int(7)];
+        assert_eq!(from_row::<ByName>(&columns(&["d.name", "d.down.rate"]), &row).unwrap(),
+                   ByName { down_rate: 7, name: "foo".to_owned() });
+        // Columns without a namespace are matched as-is.
+        assert_eq!(from_row::<ByName>(&columns(&["name", "down_rate"]), &row).unwrap(),
+                   ByName { down_rate: 7, name: "foo".to_owned() });
+        // A short row is missing a field.
+        assert!(from_row::<ByName>(&columns(&["d.name", "d.down.rate"]), &row[..1]).is_err());
+    }
+
+    #[test]
+    fn option() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Optional {
+            message: Option<String>,
+            name: Option<String>,
+        }
+        let row = [Value::Nil, Value::from("foo")];
+        assert_eq!(from_row::<Optional>(&columns(&["d.message", "d.name"]), &row).unwrap(),
+                   Optional { message: None, name: Some("foo".to_owned()) });
+    }
+
+    #[test]
+    fn bool_from_int() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Flags {
+            is_open: bool,
+            is_active: bool,
+            complete: bool,
+        }
+        let row = [Value::Int64(1), Value::Int(0), Value::Bool(true)];
+        assert_eq!(from_row::<Flags>(&columns(&["d.is_open", "d.is_active", "d.complete"]), &row)
+                   .unwrap(),
+                   Flags { is_open: true, is_active: false, complete: true });
+        let (open,): (bool,) = from_row(&columns(&["d.is_open"]), &[Value::Int64(0)]).unwrap();
+        assert!(!open);
+    }
+
+    #[test]
+    fn nested_array() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Nested {
+            views: Vec<String>,
+            path_components: Vec<String>,
+            stats: BTreeMap<String, i64>,
+        }
+        let mut stats = BTreeMap::new();
+        stats.insert("peers".to_owned(), Value::Int64(3));
+        let row = [
+            Value::Array(vec![Value::from("main"), Value::from("seeding")]),
+            Value::Array(vec![]),
+            Value::Struct(stats),
+        ];
+        let nested: Nested =
+            from_row(&columns(&["d.views", "f.path_components", "d.stats"]), &row).unwrap();
+        assert_eq!(nested.views, vec!["main", "seeding"]);
+        assert!(nested.path_components.is_empty());
+        assert_eq!(nested.stats.get("peers"), Some(&3));
+    }
+}
@@ -0,0 +1,149 @@
+//! Rtorrent multicalls with a runtime column list
+
+use crate::multicall::{raw, Order, Row};
+use crate::{value_conversion, Error, Result};
+use std::collections::BTreeMap;
+use xmlrpc::Value;
+
+/// `DynamicBuilder` is a multicall builder whose columns are chosen at runtime
+///
+/// The typed builders in [`d`], [`f`], [`p`], and [`t`] encode their columns in the builder's
+/// type, so the set of columns must be known at compile time.  A `DynamicBuilder` instead accepts
+/// any number of accessor names (e.g., `"d.name"`) and returns each row as a `Vec` of untyped
+/// [`Value`]s, in column order.
+///
+/// A `DynamicBuilder` is obtained from one of the typed builders via its `dynamic()` method.
+///
+/// ## Usage
+///
+/// ```no_run
+/// use rtorrent_xmlrpc_bindings as rtorrent;
+/// use rtorrent::multicall::d;
+///
+/// let my_handle = rtorrent::Server::new("http://1.2.3.4/RPC2");
+///
+/// // E.g., columns read from a configuration file.
+/// let columns = vec!["d.name", "d.ratio", "d.custom1"];
+///
+/// let mut builder = d::MultiBuilder::new(&my_handle, "default").dynamic();
+/// for column in &columns {
+///     builder.push(column);
+/// }
+/// for row in builder.invoke()? {
+///     println!("{:?}", row);
+/// }
+/// # Ok::<(), rtorrent::Error>(())
+/// ```
+///
+/// [`d`]: crate::multicall::d
+/// [`f`]: crate::multicall::f
+/// [`p`]: crate::multicall::p
+/// [`t`]: crate::multicall::t
+/// [`Value`]: xmlrpc::Value
+pub struct DynamicBuilder {
+    inner: raw::MultiBuilder,
+    columns: Vec<String>,
+}
+
+impl DynamicBuilder {
+    pub(crate) fn new(inner: raw::MultiBuilder) -> Self {
+        Self { inner, columns: Vec::new() }
+    }
+
+    /// Add a column (an accessor named `getter`, e.g. `"d.name"`) to the query represented by this
+    /// builder.
+    pub fn push(&mut self, getter: &str) {
+        self.inner.inner.push_arg(Value::from(format!("{}=", getter)));
+        self.columns.push(getter.to_owned());
+    }
+
+    /// Return a builder representing the result of adding a column (an accessor named `getter`)
+    /// to the query represented by this builder.
+    pub fn call(mut self, getter: &str) -> Self {
+        self.push(getter);
+        self
+    }
+
+    /// Return a builder whose results are sorted by the value of the zero-based `column`, in
+    /// `order`.  Sorting happens client-side, before any [`limit`](Self::limit) is applied.
+    pub fn sort_by_column(mut self, column: usize, order: Order) -> Self {
+        self.inner.inner.set_sort(column, order);
+        self
+    }
+
+    /// Return a builder whose results are truncated to at most `limit` rows, client-side.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.inner.inner.set_limit(limit);
+        self
+    }
+
+    /// Return a builder that checks that the server supports every column and setter before
+    /// running the query, reporting the first unsupported one as [`Error::UnsupportedMethod`].
+    ///
+    /// [`Error::UnsupportedMethod`]: crate::Error::UnsupportedMethod
+    pub fn validate(mut self) -> Self {
+        self.inner.inner.set_validate();
+        self
+    }
+
+    /// Set the value of `setter` (the full command name, e.g. `"d.priority.set"`) to `value` on
+    /// every target object, when the query is run.  Setters run after the columns are read, and
+    /// do not add columns to the result.
+    pub fn push_setter(&mut self, setter: &str, value: Value) {
+        self.inner.inner.push_setter(setter, value);
+    }
+
+    /// Get the accessor names of the columns of this query, in order.
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Run this query on the associated server and return the resulting data.
+    ///
+    /// On success, each entry in the resulting `Vec` represents a "row", corresponding to a single
+    /// target object.  Each row contains one `Value` per column, in the order the columns were
+    /// added.
+    pub fn invoke(&self) -> Result<Vec<Vec<Value>>> {
+        self.inner.inner.invoke()?
+            .into_iter()
+            .map(|row| {
+                let row = value_conversion::into_list(row)?;
+                if row.len() != self.columns.len() {
+                    return Err(Error::UnexpectedStructure(
+                        format!("row missing columns ({:?})", row)
+                    ));
+                }
+                Ok(row)
+            })
+            .collect::<Result<_>>()
+            .map_err(|e| e.in_call(self.inner.inner.context()))
+    }
+
+    /// Run this query on the associated server and return the resulting data as [`Row`]s, with
+    /// columns indexed by accessor name.
+    ///
+    /// [`Row`]: crate::multicall::Row
+    pub fn invoke_named(&self) -> Result<Vec<Row>> {
+        self.inner.inner.invoke_named()
+    }
+
+    /// Run this query on the associated server and deserialize each resulting row into a `T`,
+    /// by field name or by position (see, e.g., [`d::MultiBuilder1::invoke_into`]).
+    ///
+    /// Only available with the `serde` feature.
+    ///
+    /// [`d::MultiBuilder1::invoke_into`]: crate::multicall::d::MultiBuilder1::invoke_into
+    #[cfg(feature = "serde")]
+    pub fn invoke_into<T: serde::de::DeserializeOwned>(&self) -> Result<Vec<T>> {
+        self.inner.inner.invoke_into()
+    }
+
+    /// Run this query on the associated server and return the resulting data, with each row
+    /// represented as a map from accessor name to value.
+    pub fn invoke_maps(&self) -> Result<Vec<BTreeMap<String, Value>>> {
+        Ok(self.invoke()?
+           .into_iter()
+           .map(|row| self.columns.iter().cloned().zip(row).collect())
+           .collect())
+    }
+}
@@ -11,21 +11,56 @@
 //!
 //! See the corresponding module documentation for multicall documentation specific to that kind of
 //! query.
+//!
+//! For queries whose columns are only known at runtime, see [`DynamicBuilder`].
 
 #![allow(dead_code)]
 
+#[cfg(feature = "serde")]
+mod de;
+mod dynamic;
 mod ops;
 mod raw_impl;
+mod row;
+pub(crate) mod system;
+
+pub use dynamic::DynamicBuilder;
+pub use row::Row;
+
+/// The direction in which multicall results are sorted by `sort_by_column()`
+///
+/// ```no_run
+/// use rtorrent_xmlrpc_bindings as rtorrent;
+/// use rtorrent::multicall::{d, Order};
+///
+/// let my_handle = rtorrent::Server::new("http://1.2.3.4/RPC2");
+///
+/// // The 20 downloads with the highest upload rate.
+/// for (name, up_rate) in d::MultiBuilder::new(&my_handle, "main")
+///     .call(d::NAME)
+///     .call(d::UP_RATE)
+///     .sort_by_column(1, Order::Descending)
+///     .limit(20)
+///     .invoke()? {
+///     println!("{}: {} B/s", name, up_rate);
+/// }
+/// # Ok::<(), rtorrent::Error>(())
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Order {
+    Ascending,
+    Descending,
+}
 
 mod raw {
     use super::raw_impl;
 
-    use crate::{value_conversion::{self, TryFromValue}, Error, Result};
+    use crate::{value_conversion::{self, TryFromValue}, CallContext, Error, Result};
     use std::marker::PhantomData;
     use xmlrpc::Value;
 
     pub(crate) use raw_impl::MultiBuilder;
-    use raw_impl::MultiBuilderInternal;
+    use raw_impl::{convert_column, MultiBuilderInternal};
 
     raw_impl::define_builder!(MultiBuilder,  MultiBuilder1, | phantom_a A);
     raw_impl::define_builder!(MultiBuilder1, MultiBuilder2, phantom_a A | phantom_b B);
@@ -43,6 +78,30 @@ mod raw {
     raw_impl::define_builder!(MultiBuilder8, MultiBuilder9, phantom_a A, phantom_b B, phantom_c C,
                               phantom_d D, phantom_e E, phantom_g G, phantom_h H, phantom_i I |
                               phantom_j J);
+    raw_impl::define_builder!(MultiBuilder9, MultiBuilder10, phantom_a A, phantom_b B, phantom_c C,
+                              phantom_d D, phantom_e E, phantom_g G, phantom_h H, phantom_i I,
+                              phantom_j J | phantom_k K);
+    raw_impl::define_builder!(MultiBuilder10, MultiBuilder11, phantom_a A, phantom_b B, phantom_c C,
+                              phantom_d D, phantom_e E, phantom_g G, phantom_h H, phantom_i I,
+                              phantom_j J, phantom_k K | phantom_l L);
+    raw_impl::define_builder!(MultiBuilder11, MultiBuilder12, phantom_a A, phantom_b B, phantom_c C,
+                              phantom_d D, phantom_e E, phantom_g G, phantom_h H, phantom_i I,
+                              phantom_j J, phantom_k K, phantom_l L | phantom_m M);
+    raw_impl::define_builder!(MultiBuilder12, MultiBuilder13, phantom_a A, phantom_b B, phantom_c C,
+                              phantom_d D, phantom_e E, phantom_g G, phantom_h H, phantom_i I,
+                              phantom_j J, phantom_k K, phantom_l L, phantom_m M | phantom_n N);
+    raw_impl::define_builder!(MultiBuilder13, MultiBuilder14, phantom_a A, phantom_b B, phantom_c C,
+                              phantom_d D, phantom_e E, phantom_g G, phantom_h H, phantom_i I,
+                              phantom_j J, phantom_k K, phantom_l L, phantom_m M, phantom_n N |
+                              phantom_o O);
+    raw_impl::define_builder!(MultiBuilder14, MultiBuilder15, phantom_a A, phantom_b B, phantom_c C,
+                              phantom_d D, phantom_e E, phantom_g G, phantom_h H, phantom_i I,
+                              phantom_j J, phantom_k K, phantom_l L, phantom_m M, phantom_n N,
+                              phantom_o O | phantom_p P);
+    raw_impl::define_builder!(MultiBuilder15, MultiBuilder16, phantom_a A, phantom_b B, phantom_c C,
+                              phantom_d D, phantom_e E, phantom_g G, phantom_h H, phantom_i I,
+                              phantom_j J, phantom_k K, phantom_l L, phantom_m M, phantom_n N,
+                              phantom_o O, phantom_p P | phantom_q Q);
 }
 
 /// The `d` module builds multicalls over `Download`s
@@ -70,6 +129,26 @@ mod raw {
 /// # Ok::<(), rtorrent::Error>(())
 /// ```
 ///
+/// A query can be built once and run repeatedly against different views with `invoke_view`:
+///
+/// ```no_run
+/// use rtorrent_xmlrpc_bindings as rtorrent;
+/// use rtorrent::multicall::d;
+///
+/// let my_handle = rtorrent::Server::new("http://1.2.3.4/RPC2");
+///
+/// let query = d::MultiBuilder::new(&my_handle, "main")
+///     .call(d::NAME)
+///     .call(d::UP_RATE);
+/// loop {
+///     for view in ["leeching", "seeding", "stopped"] {
+///         println!("{}: {:?}", view, query.invoke_view(view)?);
+///     }
+///     std::thread::sleep(std::time::Duration::from_secs(5));
+/// }
+/// # Ok::<(), rtorrent::Error>(())
+/// ```
+///
 /// [`d::MultiBuilder`]: crate::multicall::d::MultiBuilder
 pub mod d {
     use crate::{value_conversion::TryFromValue, Result};
@@ -109,6 +188,39 @@ pub mod d {
         /// `MultiBuilder9` represents a nine-column query over all `Download`s in a view
         MultiBuilder8, MultiBuilder9, phantom_a A, phantom_b B, phantom_c C , phantom_d D,
         phantom_e E, phantom_g G , phantom_h H , phantom_i I | phantom_j J);
+    define_builder!(
+        /// `MultiBuilder10` represents a ten-column query over all `Download`s in a view
+        MultiBuilder9, MultiBuilder10, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_g G, phantom_h H, phantom_i I, phantom_j J | phantom_k K);
+    define_builder!(
+        /// `MultiBuilder11` represents an eleven-column query over all `Download`s in a view
+        MultiBuilder10, MultiBuilder11, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_g G, phantom_h H, phantom_i I, phantom_j J, phantom_k K | phantom_l L);
+    define_builder!(
+        /// `MultiBuilder12` represents a twelve-column query over all `Download`s in a view
+        MultiBuilder11, MultiBuilder12, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_g G, phantom_h H, phantom_i I, phantom_j J, phantom_k K, phantom_l L |
+        phantom_m M);
+    define_builder!(
+        /// `MultiBuilder13` represents a thirteen-column query over all `Download`s in a view
+        MultiBuilder12, MultiBuilder13, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_g G, phantom_h H, phantom_i I, phantom_j J, phantom_k K, phantom_l L,
+        phantom_m M | phantom_n N);
+    define_builder!(
+        /// `MultiBuilder14` represents a fourteen-column query over all `Download`s in a view
+        MultiBuilder13, MultiBuilder14, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_g G, phantom_h H, phantom_i I, phantom_j J, phantom_k K, phantom_l L,
+        phantom_m M, phantom_n N | phantom_o O);
+    define_builder!(
+        /// `MultiBuilder15` represents a fifteen-column query over all `Download`s in a view
+        MultiBuilder14, MultiBuilder15, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_g G, phantom_h H, phantom_i I, phantom_j J, phantom_k K, phantom_l L,
+        phantom_m M, phantom_n N, phantom_o O | phantom_p P);
+    define_builder!(
+        /// `MultiBuilder16` represents a sixteen-column query over all `Download`s in a view
+        MultiBuilder15, MultiBuilder16, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_g G, phantom_h H, phantom_i I, phantom_j J, phantom_k K, phantom_l L,
+        phantom_m M, phantom_n N, phantom_o O, phantom_p P | phantom_q Q);
 }
 
 /// The `f` module builds multicalls over `File`s in a `Download`
@@ -159,6 +271,55 @@ pub mod f {
         /// `MultiBuilder5` represents a five-column query over all `File`s in a `Download`
         MultiBuilder4, MultiBuilder5, phantom_a A, phantom_b B, phantom_c C , phantom_d D |
         phantom_e E);
+    define_builder!(
+        /// `MultiBuilder6` represents a six-column query over all `File`s in a `Download`
+        MultiBuilder5, MultiBuilder6, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E | phantom_g G);
+    define_builder!(
+        /// `MultiBuilder7` represents a seven-column query over all `File`s in a `Download`
+        MultiBuilder6, MultiBuilder7, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_g G | phantom_h H);
+    define_builder!(
+        /// `MultiBuilder8` represents an eight-column query over all `File`s in a `Download`
+        MultiBuilder7, MultiBuilder8, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_g G, phantom_h H | phantom_i I);
+    define_builder!(
+        /// `MultiBuilder9` represents a nine-column query over all `File`s in a `Download`
+        MultiBuilder8, MultiBuilder9, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_g G, phantom_h H, phantom_i I | phantom_j J);
+    define_builder!(
+        /// `MultiBuilder10` represents a ten-column query over all `File`s in a `Download`
+        MultiBuilder9, MultiBuilder10, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_g G, phantom_h H, phantom_i I, phantom_j J | phantom_k K);
+    define_builder!(
+        /// `MultiBuilder11` represents an eleven-column query over all `File`s in a `Download`
+        MultiBuilder10, MultiBuilder11, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_g G, phantom_h H, phantom_i I, phantom_j J, phantom_k K | phantom_l L);
+    define_builder!(
+        /// `MultiBuilder12` represents a twelve-column query over all `File`s in a `Download`
+        MultiBuilder11, MultiBuilder12, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_g G, phantom_h H, phantom_i I, phantom_j J, phantom_k K, phantom_l L |
+        phantom_m M);
+    define_builder!(
+        /// `MultiBuilder13` represents a thirteen-column query over all `File`s in a `Download`
+        MultiBuilder12, MultiBuilder13, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_g G, phantom_h H, phantom_i I, phantom_j J, phantom_k K, phantom_l L,
+        phantom_m M | phantom_n N);
+    define_builder!(
+        /// `MultiBuilder14` represents a fourteen-column query over all `File`s in a `Download`
+        MultiBuilder13, MultiBuilder14, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_g G, phantom_h H, phantom_i I, phantom_j J, phantom_k K, phantom_l L,
+        phantom_m M, phantom_n N | phantom_o O);
+    define_builder!(
+        /// `MultiBuilder15` represents a fifteen-column query over all `File`s in a `Download`
+        MultiBuilder14, MultiBuilder15, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_g G, phantom_h H, phantom_i I, phantom_j J, phantom_k K, phantom_l L,
+        phantom_m M, phantom_n N, phantom_o O | phantom_p P);
+    define_builder!(
+        /// `MultiBuilder16` represents a sixteen-column query over all `File`s in a `Download`
+        MultiBuilder15, MultiBuilder16, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_g G, phantom_h H, phantom_i I, phantom_j J, phantom_k K, phantom_l L,
+        phantom_m M, phantom_n N, phantom_o O, phantom_p P | phantom_q Q);
 }
 
 /// The `p` module builds multicalls over `Peer`s on a `Download`
@@ -222,7 +383,61 @@ pub mod p {
         /// `Download`
         MultiBuilder5, MultiBuilder6, phantom_a A, phantom_b B, phantom_c C , phantom_d D,
         phantom_e E | phantom_f F);
-
+    define_builder!(
+        /// `MultiBuilder7` represents a seven-column query over all swarm `Peers` associated with a
+        /// `Download`
+        MultiBuilder6, MultiBuilder7, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_f F | phantom_g G);
+    define_builder!(
+        /// `MultiBuilder8` represents an eight-column query over all swarm `Peers` associated with
+        /// a `Download`
+        MultiBuilder7, MultiBuilder8, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_f F, phantom_g G | phantom_h H);
+    define_builder!(
+        /// `MultiBuilder9` represents a nine-column query over all swarm `Peers` associated with a
+        /// `Download`
+        MultiBuilder8, MultiBuilder9, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_f F, phantom_g G, phantom_h H | phantom_i I);
+    define_builder!(
+        /// `MultiBuilder10` represents a ten-column query over all swarm `Peers` associated with a
+        /// `Download`
+        MultiBuilder9, MultiBuilder10, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_f F, phantom_g G, phantom_h H, phantom_i I | phantom_j J);
+    define_builder!(
+        /// `MultiBuilder11` represents an eleven-column query over all swarm `Peers` associated
+        /// with a `Download`
+        MultiBuilder10, MultiBuilder11, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_f F, phantom_g G, phantom_h H, phantom_i I, phantom_j J | phantom_k K);
+    define_builder!(
+        /// `MultiBuilder12` represents a twelve-column query over all swarm `Peers` associated with
+        /// a `Download`
+        MultiBuilder11, MultiBuilder12, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_f F, phantom_g G, phantom_h H, phantom_i I, phantom_j J, phantom_k K |
+        phantom_l L);
+    define_builder!(
+        /// `MultiBuilder13` represents a thirteen-column query over all swarm `Peers` associated
+        /// with a `Download`
+        MultiBuilder12, MultiBuilder13, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_f F, phantom_g G, phantom_h H, phantom_i I, phantom_j J, phantom_k K,
+        phantom_l L | phantom_m M);
+    define_builder!(
+        /// `MultiBuilder14` represents a fourteen-column query over all swarm `Peers` associated
+        /// with a `Download`
+        MultiBuilder13, MultiBuilder14, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_f F, phantom_g G, phantom_h H, phantom_i I, phantom_j J, phantom_k K,
+        phantom_l L, phantom_m M | phantom_n N);
+    define_builder!(
+        /// `MultiBuilder15` represents a fifteen-column query over all swarm `Peers` associated
+        /// with a `Download`
+        MultiBuilder14, MultiBuilder15, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_f F, phantom_g G, phantom_h H, phantom_i I, phantom_j J, phantom_k K,
+        phantom_l L, phantom_m M, phantom_n N | phantom_o O);
+    define_builder!(
+        /// `MultiBuilder16` represents a sixteen-column query over all swarm `Peers` associated
+        /// with a `Download`
+        MultiBuilder15, MultiBuilder16, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_f F, phantom_g G, phantom_h H, phantom_i I, phantom_j J, phantom_k K,
+        phantom_l L, phantom_m M, phantom_n N, phantom_o O | phantom_p P);
 }
 
 /// The `t` module builds multicalls over `Trackers`s associated with a `Download`
@@ -271,4 +486,57 @@ pub mod t {
     define_builder!(
         /// `MultiBuilder4` represents a four-column query over all `Tracker`s for a `Download`
         MultiBuilder3, MultiBuilder4, phantom_a A, phantom_b B, phantom_c C | phantom_d D);
+    define_builder!(
+        /// `MultiBuilder5` represents a five-column query over all `Tracker`s for a `Download`
+        MultiBuilder4, MultiBuilder5, phantom_a A, phantom_b B, phantom_c C, phantom_d D |
+        phantom_e E);
+    define_builder!(
+        /// `MultiBuilder6` represents a six-column query over all `Tracker`s for a `Download`
+        MultiBuilder5, MultiBuilder6, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E | phantom_g G);
+    define_builder!(
+        /// `MultiBuilder7` represents a seven-column query over all `Tracker`s for a `Download`
+        MultiBuilder6, MultiBuilder7, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_g G | phantom_h H);
+    define_builder!(
+        /// `MultiBuilder8` represents an eight-column query over all `Tracker`s for a `Download`
+        MultiBuilder7, MultiBuilder8, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_g G, phantom_h H | phantom_i I);
+    define_builder!(
+        /// `MultiBuilder9` represents a nine-column query over all `Tracker`s for a `Download`
+        MultiBuilder8, MultiBuilder9, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_g G, phantom_h H, phantom_i I | phantom_j J);
+    define_builder!(
+        /// `MultiBuilder10` represents a ten-column query over all `Tracker`s for a `Download`
+        MultiBuilder9, MultiBuilder10, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_g G, phantom_h H, phantom_i I, phantom_j J | phantom_k K);
+    define_builder!(
+        /// `MultiBuilder11` represents an eleven-column query over all `Tracker`s for a `Download`
+        MultiBuilder10, MultiBuilder11, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_g G, phantom_h H, phantom_i I, phantom_j J, phantom_k K | phantom_l L);
+    define_builder!(
+        /// `MultiBuilder12` represents a twelve-column query over all `Tracker`s for a `Download`
+        MultiBuilder11, MultiBuilder12, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_g G, phantom_h H, phantom_i I, phantom_j J, phantom_k K, phantom_l L |
+        phantom_m M);
+    define_builder!(
+        /// `MultiBuilder13` represents a thirteen-column query over all `Tracker`s for a `Download`
+        MultiBuilder12, MultiBuilder13, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_g G, phantom_h H, phantom_i I, phantom_j J, phantom_k K, phantom_l L,
+        phantom_m M | phantom_n N);
+    define_builder!(
+        /// `MultiBuilder14` represents a fourteen-column query over all `Tracker`s for a `Download`
+        MultiBuilder13, MultiBuilder14, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_g G, phantom_h H, phantom_i I, phantom_j J, phantom_k K, phantom_l L,
+        phantom_m M, phantom_n N | phantom_o O);
+    define_builder!(
+        /// `MultiBuilder15` represents a fifteen-column query over all `Tracker`s for a `Download`
+        MultiBuilder14, MultiBuilder15, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_g G, phantom_h H, phantom_i I, phantom_j J, phantom_k K, phantom_l L,
+        phantom_m M, phantom_n N, phantom_o O | phantom_p P);
+    define_builder!(
+        /// `MultiBuilder16` represents a sixteen-column query over all `Tracker`s for a `Download`
+        MultiBuilder15, MultiBuilder16, phantom_a A, phantom_b B, phantom_c C, phantom_d D,
+        phantom_e E, phantom_g G, phantom_h H, phantom_i I, phantom_j J, phantom_k K, phantom_l L,
+        phantom_m M, phantom_n N, phantom_o O, phantom_p P | phantom_q Q);
 }
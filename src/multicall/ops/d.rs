@@ -1,8 +1,9 @@
 //! Rtorrent d.* multicall operations
 
-use crate::{multicall::raw, Server};
+use crate::{multicall::{raw, DynamicBuilder}, Priority, Result, Server};
 use std::borrow::Cow;
 use std::marker::PhantomData;
+use xmlrpc::Value;
 
 super::op_type! {
     /// A `d.*` operation for multicalls
@@ -62,11 +63,89 @@ impl MultiBuilder {
             inner: raw::MultiBuilder::new(server, "d.multicall2", "", view),
         }
     }
+
+    /// Start building a multicall over exactly the downloads identified by `hashes` (infohashes
+    /// in hex, as returned by [`Download::sha1_hex`]) on `server`, rather than over a view.
+    ///
+    /// Rows are returned in the order of `hashes`.  The query is issued as a single
+    /// `system.multicall` of each column's accessor against each download; if any download does
+    /// not exist, the entire query fails.
+    ///
+    /// ```no_run
+    /// use rtorrent_xmlrpc_bindings as rtorrent;
+    /// use rtorrent::multicall::d;
+    ///
+    /// let my_handle = rtorrent::Server::new("http://1.2.3.4/RPC2");
+    /// let watched = ["0123456789ABCDEF0123456789ABCDEF01234567"];
+    ///
+    /// for (name, ratio) in d::MultiBuilder::for_hashes(&my_handle, &watched)
+    ///     .call(d::NAME)
+    ///     .call(d::RATIO)
+    ///     .invoke()? {
+    ///     println!("{}: {} ratio", name, ratio);
+    /// }
+    /// # Ok::<(), rtorrent::Error>(())
+    /// ```
+    ///
+    /// [`Download::sha1_hex`]: crate::Download::sha1_hex
+    pub fn for_hashes(server: &Server, hashes: &[&str]) -> Self {
+        let targets = hashes.iter()
+            .map(|hash| Value::from(hash.to_ascii_uppercase()))
+            .collect();
+        Self {
+            inner: raw::MultiBuilder::with_targets(server, targets),
+        }
+    }
+
+    /// Split the query into batches of at most `chunk_size` downloads each, for instances with
+    /// very many downloads, where a single multicall response may exceed XMLRPC size limits or
+    /// stall rtorrent.
+    ///
+    /// The downloads in the view are first listed by hash; then each batch is queried with a
+    /// single `system.multicall`, and the results are concatenated in view order.  Each batch is
+    /// a separate round trip.  A download removed from rtorrent while the query is in progress
+    /// causes the query to fail.
+    pub fn chunked(mut self, chunk_size: usize) -> Self {
+        self.inner.inner.set_chunk_size(chunk_size);
+        self
+    }
+
+    /// Convert this builder into a [`DynamicBuilder`], whose columns are chosen at runtime.
+    ///
+    /// [`DynamicBuilder`]: crate::multicall::DynamicBuilder
+    pub fn dynamic(self) -> DynamicBuilder {
+        DynamicBuilder::new(self.inner)
+    }
 }
 
+super::define_base_setters!(DownloadMultiCallOp);
+
 macro_rules! define_builder {
     ( $(#[$meta:meta])* $prev: ident, $name: ident, $($phantoms:ident $ty:ident),* | $phantom_last:ident $ty_last:ident ) => {
         ops::define_builder!($(#[$meta])* DownloadMultiCallOp, $prev, $name, $($phantoms $ty),* | $phantom_last $ty_last);
+
+        impl<$($ty: TryFromValue,)* $ty_last: TryFromValue> $name<$($ty,)* $ty_last> {
+            /// Run this query over the downloads in `view`, rather than the view the builder was
+            /// created with.
+            ///
+            /// This allows building a set of columns once and running it repeatedly, e.g., in a
+            /// polling loop over several views.
+            pub fn invoke_view(&self, view: &str) -> Result<Vec<($($ty,)* $ty_last,)>> {
+                self.inner.invoke_retargeted("d.multicall2", "", view)
+            }
+
+            /// Run this query over the downloads in each of `views`, in a single round trip.
+            ///
+            /// The results for each view are returned in the order of `views`.  The query is
+            /// always run against views; [`for_hashes`] and [`chunked`] do not apply.
+            ///
+            /// [`for_hashes`]: crate::multicall::d::MultiBuilder::for_hashes
+            /// [`chunked`]: crate::multicall::d::MultiBuilder::chunked
+            pub fn invoke_views(&self, views: &[&str])
+                -> Result<Vec<Vec<($($ty,)* $ty_last,)>>> {
+                self.inner.invoke_retargeted_filters("d.multicall2", "", views)
+            }
+        }
     }
 }
 pub(crate) use define_builder;
@@ -106,18 +185,47 @@ d_op_const!(
 d_op_const!(
     /// The number of completed chunks (pieces).
     COMPLETED_CHUNKS, i64, "completed_chunks");
+d_op_const!(
+    /// Get the `custom1` string, an arbitrary user-defined value.  ruTorrent uses this for the
+    /// (URL-encoded) label.
+    CUSTOM1, String, "custom1");
+d_op_const!(
+    /// Get the `custom2` string, an arbitrary user-defined value.
+    CUSTOM2, String, "custom2");
+d_op_const!(
+    /// Get the `custom3` string, an arbitrary user-defined value.
+    CUSTOM3, String, "custom3");
+d_op_const!(
+    /// Get the `custom4` string, an arbitrary user-defined value.
+    CUSTOM4, String, "custom4");
+d_op_const!(
+    /// Get the `custom5` string, an arbitrary user-defined value.
+    CUSTOM5, String, "custom5");
 d_op_const!(
     /// Get the download rate.
     DOWN_RATE, i64, "down.rate");
 d_op_const!(
     /// Get the download total (bytes).
     DOWN_TOTAL, i64, "down.total");
+d_op_const!(
+    /// Get the free space, in bytes, on the filesystem containing the download's data.
+    FREE_DISKSPACE, i64, "free_diskspace");
 d_op_const!(
     /// Is this torrent active?
     IS_ACTIVE, bool, "is_active");
+d_op_const!(
+    /// Is this a multi-file torrent?
+    IS_MULTI_FILE, bool, "is_multi_file");
+d_op_const!(
+    /// Is this torrent marked private?
+    IS_PRIVATE, bool, "is_private");
 d_op_const!(
     /// Is the download currently hash-checking?
     IS_HASH_CHECKING, bool, "is_hash_checking");
+d_op_const!(
+    /// Get the hash-checking state: 0, not hashing; 1, initial check; 2, re-check after
+    /// completion; or 3, re-check requested by the user.
+    HASHING, i64, "hashing");
 d_op_const!(
     IS_OPEN, bool, "is_open");
 d_op_const!(
@@ -132,6 +240,18 @@ d_op_const!(
 d_op_const!(
     /// Get the name of the torrent.
     NAME, String, "name");
+d_op_const!(
+    /// Get the number of peers currently connected.
+    PEERS_CONNECTED, i64, "peers_connected");
+d_op_const!(
+    /// Get the number of connected peers that have the complete download (seeders).
+    PEERS_COMPLETE, i64, "peers_complete");
+d_op_const!(
+    /// Get the number of connected peers that do not have the complete download (leechers).
+    PEERS_ACCOUNTED, i64, "peers_accounted");
+d_op_const!(
+    /// Get the download priority.
+    PRIORITY, Priority, "priority");
 d_op_const!(
     /// Get the upload/download ratio for this download.
     RATIO, f64, "ratio");
@@ -144,9 +264,21 @@ d_op_const!(
 d_op_const!(
     /// Get the state (`false` is stopped).
     STATE, bool, "state");
+d_op_const!(
+    /// Get the time the state (started or stopped) last changed, in seconds since Unix epoch.
+    STATE_CHANGED, i64, "state_changed");
 d_op_const!(
     /// Starts as the file the download was initially created from.
     TIED_TO_FILE, String, "tied_to_file");
+d_op_const!(
+    /// Get the name of the throttle group the download is assigned to (empty for none).
+    THROTTLE_NAME, String, "throttle_name");
+d_op_const!(
+    /// Get the timestamp at which the download finished (zero if it has not).
+    TIMESTAMP_FINISHED, i64, "timestamp.finished");
+d_op_const!(
+    /// Get the timestamp at which the download was last started.
+    TIMESTAMP_STARTED, i64, "timestamp.started");
 d_op_const!(
     /// Get the number of trackers associated with this download.
     TRACKER_SIZE, i64, "tracker_size");
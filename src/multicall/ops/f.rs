@@ -1,8 +1,9 @@
 //! Rtorrent f.* multicall operations
 
-use crate::{multicall::raw, Server};
+use crate::{multicall::{raw, DynamicBuilder}, FilePriority, Result, Server};
 use std::borrow::Cow;
 use std::marker::PhantomData;
+use xmlrpc::Value;
 
 super::op_type! {
     /// An `f.*` operation for multicalls
@@ -59,11 +60,32 @@ impl MultiBuilder {
             inner: raw::MultiBuilder::new(server, "f.multicall", download_sha1, glob.unwrap_or("")),
         }
     }
+
+    /// Convert this builder into a [`DynamicBuilder`], whose columns are chosen at runtime.
+    ///
+    /// [`DynamicBuilder`]: crate::multicall::DynamicBuilder
+    pub fn dynamic(self) -> DynamicBuilder {
+        DynamicBuilder::new(self.inner)
+    }
 }
 
+super::define_base_setters!(FileMultiCallOp);
+
 macro_rules! define_builder {
     ( $(#[$meta:meta])* $prev: ident, $name: ident, $($phantoms:ident $ty:ident),* | $phantom_last:ident $ty_last:ident ) => {
         ops::define_builder!($(#[$meta])* FileMultiCallOp, $prev, $name, $($phantoms $ty),* | $phantom_last $ty_last);
+
+        impl<$($ty: TryFromValue,)* $ty_last: TryFromValue> $name<$($ty,)* $ty_last> {
+            /// Run this query over the files of the download identified by `download_sha1`
+            /// (optionally filtered by `glob`), rather than the download the builder was created
+            /// with.
+            ///
+            /// This allows building a set of columns once and running it repeatedly.
+            pub fn invoke_download(&self, download_sha1: &str, glob: Option<&str>)
+                -> Result<Vec<($($ty,)* $ty_last,)>> {
+                self.inner.invoke_retargeted("f.multicall", download_sha1, glob.unwrap_or(""))
+            }
+        }
     }
 }
 pub(crate) use define_builder;
@@ -81,12 +103,38 @@ f_op_const!(
 f_op_const!(
     /// Get the absolute path of this file.
     FROZEN_PATH, String, "frozen_path");
+f_op_const!(
+    /// Has this file been created on disk?
+    IS_CREATED, bool, "is_created");
+f_op_const!(
+    /// Is this file currently open?
+    IS_OPEN, bool, "is_open");
+f_op_const!(
+    /// The time rtorrent last wrote to (touched) this file, in microseconds since Unix epoch.
+    LAST_TOUCHED, i64, "last_touched");
+f_op_const!(
+    /// The number of leading path components this file shares with the next file in the
+    /// download.
+    MATCH_DEPTH_NEXT, i64, "match_depth_next");
+f_op_const!(
+    /// The number of leading path components this file shares with the previous file in the
+    /// download.
+    MATCH_DEPTH_PREV, i64, "match_depth_prev");
 f_op_const!(
     /// The offset (in bytes) of the file from the start of the torrent data.
     OFFSET, i64, "offset");
 f_op_const!(
     /// Get the path of this file, relative to the download's base path.
     PATH, String, "path");
+f_op_const!(
+    /// Get the path of this file, relative to the download's base path, as a list of components.
+    PATH_COMPONENTS, Vec<String>, "path_components");
+f_op_const!(
+    /// Are the first chunks of this file downloaded before other chunks?
+    PRIORITIZE_FIRST, bool, "prioritize_first");
+f_op_const!(
+    /// Are the last chunks of this file downloaded before other chunks?
+    PRIORITIZE_LAST, bool, "prioritize_last");
 f_op_const!(
     /// The priority of the file.
     ///
@@ -94,6 +142,17 @@ f_op_const!(
     /// * `1`: Normal.
     /// * `2`: High. Prioritize this file's chunks over "Normal" files.
     PRIORITY, i64, "priority");
+f_op_const!(
+    /// The priority of the file, as a [`FilePriority`].
+    ///
+    /// [`FilePriority`]: crate::FilePriority
+    PRIORITY_LEVEL, FilePriority, "priority");
+f_op_const!(
+    /// The index of the first chunk containing data from this file.
+    RANGE_FIRST, i64, "range_first");
+f_op_const!(
+    /// The index one past the last chunk containing data from this file.
+    RANGE_SECOND, i64, "range_second");
 f_op_const!(
     /// Get the size of the file, in bytes.
     SIZE_BYTES, i64, "size_bytes");
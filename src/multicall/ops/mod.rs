@@ -13,6 +13,26 @@ macro_rules! op_type {
             pub(crate) name: Cow<'static, str>,
             phantom: PhantomData<T>,
         }
+
+        impl<T> $name<T> {
+            /// Get the name of the rtorrent accessor this operation represents.
+            pub fn name(&self) -> &str {
+                &self.name
+            }
+        }
+
+        impl $name<i64> {
+            /// Get a variant of this operation whose result is a [`Timestamp`], for operations
+            /// that return seconds since Unix epoch.
+            ///
+            /// [`Timestamp`]: crate::Timestamp
+            pub fn as_timestamp(&self) -> $name<crate::Timestamp> {
+                $name {
+                    name: self.name.clone(),
+                    phantom: PhantomData,
+                }
+            }
+        }
     };
 }
 use op_type;
@@ -29,6 +49,30 @@ macro_rules! op_const {
 }
 use op_const;
 
+// Setters on the zero-column builders, which otherwise only add columns.
+macro_rules! define_base_setters {
+    ( $optype: ident ) => {
+        impl MultiBuilder {
+            /// Return a builder that sets the value of `setter` (e.g., `PRIORITY`) to `value` on
+            /// every target object, when the query is run.  Any number of setters may be added,
+            /// and they can be combined with "columns" added by `call()`.
+            ///
+            /// Setters do not add "columns" to the result.
+            pub fn set<T: Into<Value>>(mut self, setter: $optype<T>, value: T) -> Self {
+                self.inner.inner.push_setter(&format!("{}.set", setter.name), value.into());
+                self
+            }
+
+            /// Run this query (usually, only setters) on the associated server and return the
+            /// number of target objects it applied to.
+            pub fn invoke(&self) -> Result<usize> {
+                Ok(self.inner.inner.invoke()?.len())
+            }
+        }
+    };
+}
+use define_base_setters;
+
 macro_rules! define_builder {
     // The pipe is an ugly kludge to allow us to list types left-to-right but avoid Rust macro
     // parsing ambiguity.
@@ -49,6 +93,88 @@ macro_rules! define_builder {
             pub fn invoke(&self) -> Result<Vec<($($ty,)* $ty_last,)>> {
                 self.inner.invoke()
             }
+
+            /// Run this query on the associated server and deserialize each resulting row into
+            /// a `T`.
+            ///
+            /// If every field of a struct `T` matches a column, by accessor name (e.g.,
+            /// `d.down.rate` matches a field named `down_rate`), fields are filled by name.
+            /// Otherwise, `T` is filled by column position.
+            ///
+            /// Only available with the `serde` feature.
+            #[cfg(feature = "serde")]
+            pub fn invoke_into<T: serde::de::DeserializeOwned>(&self) -> Result<Vec<T>> {
+                self.inner.invoke_into()
+            }
+
+            /// Run this query on the associated server and return an iterator over the resulting
+            /// rows.
+            ///
+            /// Unlike [`invoke`](Self::invoke), rows are converted to their column types lazily,
+            /// as the iterator is consumed, rather than collected into one large `Vec`.  This
+            /// avoids holding every typed row in memory at once for very large views.  A row that
+            /// fails to convert yields an `Err` item.
+            pub fn invoke_iter(&self)
+                -> Result<impl Iterator<Item = Result<($($ty,)* $ty_last,)>>> {
+                self.inner.invoke_iter()
+            }
+
+            /// Run this query on the associated server and return the resulting data as
+            /// [`Row`]s, with columns indexed by accessor name (e.g., `"d.name"`) rather than
+            /// tuple position.
+            ///
+            /// [`Row`]: crate::multicall::Row
+            pub fn invoke_named(&self) -> Result<Vec<crate::multicall::Row>> {
+                self.inner.invoke_named()
+            }
+
+            /// Return a builder whose results are sorted by the value of the zero-based
+            /// `column`, in `order`.
+            ///
+            /// Sorting happens client-side, after the query is run, and before any
+            /// [`limit`](Self::limit) is applied.  Rows with equal values keep their original
+            /// order.
+            pub fn sort_by_column(self, column: usize, order: crate::multicall::Order) -> Self {
+                Self {
+                    inner: self.inner.sort_by_column(column, order)
+                }
+            }
+
+            /// Return a builder that, before running the query, checks that the server supports
+            /// every column (and setter), using the server's cached
+            /// [`supported_methods`](crate::Server::supported_methods).
+            ///
+            /// Without validation, a single unsupported column fails the whole multicall with
+            /// an opaque fault.  With it, the query fails with [`Error::UnsupportedMethod`]
+            /// naming the offending column.
+            ///
+            /// [`Error::UnsupportedMethod`]: crate::Error::UnsupportedMethod
+            pub fn validate(self) -> Self {
+                Self {
+                    inner: self.inner.validate()
+                }
+            }
+
+            /// Return a builder whose results are truncated to at most `limit` rows (e.g., for
+            /// a "top 20 by upload rate" query, with [`sort_by_column`](Self::sort_by_column)).
+            ///
+            /// Truncation happens client-side, after the query is run.
+            pub fn limit(self, limit: usize) -> Self {
+                Self {
+                    inner: self.inner.limit(limit)
+                }
+            }
+
+            /// Return a builder that also sets the value of `setter` (e.g., `PRIORITY`) to
+            /// `value` on every target object, when the query is run.
+            ///
+            /// Setters run after the query's "columns" are read, so the returned rows reflect
+            /// values from before the change.  Setters do not add "columns" to the result.
+            pub fn set<T: Into<xmlrpc::Value>>(self, setter: $optype<T>, value: T) -> Self {
+                Self {
+                    inner: self.inner.set(&format!("{}.set", setter.name), value.into())
+                }
+            }
         }
 
         impl<$($ty: TryFromValue,)*> $prev<$($ty,)*> {
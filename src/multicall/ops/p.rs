@@ -1,8 +1,10 @@
 //! Rtorrent p.* multicall operations
 
-use crate::{multicall::raw, Server};
+use crate::{multicall::{raw, DynamicBuilder}, Result, Server};
 use std::borrow::Cow;
 use std::marker::PhantomData;
+use std::net::IpAddr;
+use xmlrpc::Value;
 
 super::op_type! {
     /// A `p.*` operation for multicalls
@@ -60,11 +62,31 @@ impl MultiBuilder {
             inner: raw::MultiBuilder::new(server, "p.multicall", download_sha1, ""),
         }
     }
+
+    /// Convert this builder into a [`DynamicBuilder`], whose columns are chosen at runtime.
+    ///
+    /// [`DynamicBuilder`]: crate::multicall::DynamicBuilder
+    pub fn dynamic(self) -> DynamicBuilder {
+        DynamicBuilder::new(self.inner)
+    }
 }
 
+super::define_base_setters!(PeerMultiCallOp);
+
 macro_rules! define_builder {
     ( $(#[$meta:meta])* $prev: ident, $name: ident, $($phantoms:ident $ty:ident),* | $phantom_last:ident $ty_last:ident ) => {
         ops::define_builder!($(#[$meta])* PeerMultiCallOp, $prev, $name, $($phantoms $ty),* | $phantom_last $ty_last);
+
+        impl<$($ty: TryFromValue,)* $ty_last: TryFromValue> $name<$($ty,)* $ty_last> {
+            /// Run this query over the peers of the download identified by `download_sha1`,
+            /// rather than the download the builder was created with.
+            ///
+            /// This allows building a set of columns once and running it repeatedly.
+            pub fn invoke_download(&self, download_sha1: &str)
+                -> Result<Vec<($($ty,)* $ty_last,)>> {
+                self.inner.invoke_retargeted("p.multicall", download_sha1, "")
+            }
+        }
     }
 }
 pub(crate) use define_builder;
@@ -78,6 +100,9 @@ macro_rules! p_op_const {
 p_op_const!(
     /// Get the IP address of the peer.
     ADDRESS, String, "address");
+p_op_const!(
+    /// Get the IP address of the peer, parsed.
+    IP_ADDRESS, IpAddr, "address");
 p_op_const!(
     /// Is the peer banned, e.g., for sending "too much" corrupt data?
     BANNED, bool, "banned");
@@ -1,8 +1,9 @@
 //! Rtorrent t.* multicall operations
 
-use crate::{multicall::raw, Server};
+use crate::{multicall::{raw, DynamicBuilder}, Result, Server, TrackerType};
 use std::borrow::Cow;
 use std::marker::PhantomData;
+use xmlrpc::Value;
 
 super::op_type! {
     /// A `t.*` operation for multicalls
@@ -58,11 +59,41 @@ impl MultiBuilder {
             inner: raw::MultiBuilder::new(server, "t.multicall", download_sha1, ""),
         }
     }
+
+    /// Convert this builder into a [`DynamicBuilder`], whose columns are chosen at runtime.
+    ///
+    /// [`DynamicBuilder`]: crate::multicall::DynamicBuilder
+    pub fn dynamic(self) -> DynamicBuilder {
+        DynamicBuilder::new(self.inner)
+    }
 }
 
+super::define_base_setters!(TrackerMultiCallOp);
+
 macro_rules! define_builder {
     ( $(#[$meta:meta])* $prev: ident, $name: ident, $($phantoms:ident $ty:ident),* | $phantom_last:ident $ty_last:ident ) => {
         ops::define_builder!($(#[$meta])* TrackerMultiCallOp, $prev, $name, $($phantoms $ty),* | $phantom_last $ty_last);
+
+        impl<$($ty: TryFromValue,)* $ty_last: TryFromValue> $name<$($ty,)* $ty_last> {
+            /// Run this query over the trackers of the download identified by `download_sha1`,
+            /// rather than the download the builder was created with.
+            ///
+            /// This allows building a set of columns once and running it repeatedly.
+            pub fn invoke_download(&self, download_sha1: &str)
+                -> Result<Vec<($($ty,)* $ty_last,)>> {
+                self.inner.invoke_retargeted("t.multicall", download_sha1, "")
+            }
+
+            /// Run this query over the trackers of each of the downloads identified by
+            /// `download_sha1s`, in a single round trip.
+            ///
+            /// The results for each download are returned in the order of `download_sha1s`.  If
+            /// any download does not exist, the entire query fails.
+            pub fn invoke_downloads(&self, download_sha1s: &[&str])
+                -> Result<Vec<Vec<($($ty,)* $ty_last,)>>> {
+                self.inner.invoke_retargeted_targets("t.multicall", download_sha1s, "")
+            }
+        }
     }
 }
 pub(crate) use define_builder;
@@ -76,20 +107,75 @@ macro_rules! t_op_const {
 t_op_const!(
     /// Get the URL of the tracker.
     URL, String, "url");
+t_op_const!(
+    /// Get the protocol used to communicate with the tracker.
+    TYPE, TrackerType, "type");
 t_op_const!(
     /// Get the last time rtorrent attempted to contact this tracker, regardless of whether the
     /// attempt succeeded.  The value is in seconds since Unix epoch.
-    ACTIVTY_TIME_LAST, i64, "activity_time_last");
+    ACTIVITY_TIME_LAST, i64, "activity_time_last");
 t_op_const!(
     /// Get the next time rtorrent will attempt to contact tracker.  The value is in seconds since
     /// Unix epoch.
+    ACTIVITY_TIME_NEXT, i64, "activity_time_next");
+t_op_const!(
+    #[deprecated(note = "misspelled; use ACTIVITY_TIME_LAST")]
+    ACTIVTY_TIME_LAST, i64, "activity_time_last");
+t_op_const!(
+    #[deprecated(note = "misspelled; use ACTIVITY_TIME_NEXT")]
     ACTIVTY_TIME_NEXT, i64, "activity_time_next");
+t_op_const!(
+    /// Get the number of successful announces to the tracker.
+    SUCCESS_COUNTER, i64, "success_counter");
+t_op_const!(
+    /// Get the number of failed announces to the tracker.
+    FAILED_COUNTER, i64, "failed_counter");
+t_op_const!(
+    /// Get the number of successful scrapes of the tracker.
+    SCRAPE_COUNTER, i64, "scrape_counter");
+t_op_const!(
+    /// Is the tracker enabled?
+    IS_ENABLED, bool, "is_enabled");
+t_op_const!(
+    /// Is the tracker usable (enabled, and with a valid URL)?
+    IS_USABLE, bool, "is_usable");
+t_op_const!(
+    /// Is a request to the tracker currently in progress?
+    IS_BUSY, bool, "is_busy");
+t_op_const!(
+    /// Is a connection to the tracker currently open?
+    IS_OPEN, bool, "is_open");
+t_op_const!(
+    /// Does the tracker support scrape requests?
+    CAN_SCRAPE, bool, "can_scrape");
+t_op_const!(
+    /// Get the minimum interval between announces requested by the tracker, in seconds.
+    MIN_INTERVAL, i64, "min_interval");
+t_op_const!(
+    /// Get the regular interval between announces requested by the tracker, in seconds.
+    NORMAL_INTERVAL, i64, "normal_interval");
 t_op_const!(
     /// Get the BEP 12 group id.  Up to 32 groups are supported, starting from zero.
     GROUP, i64, "group");
 t_op_const!(
     /// Get the tracker ID (from HTTP 'tracker id' key).
     ID, String, "id");
+t_op_const!(
+    /// Get the event sent with the most recent announce: 0, none (regular update); 1, completed;
+    /// 2, started; 3, stopped; or 4, scrape.
+    LATEST_EVENT, i64, "latest_event");
+t_op_const!(
+    /// Get the number of new peers received in the most recent announce.
+    LATEST_NEW_PEERS, i64, "latest_new_peers");
 t_op_const!(
     /// Get the total number of peers as of the most recent tracker announce.
     LATEST_SUM_PEERS, i64, "latest_sum_peers");
+t_op_const!(
+    /// Get the number of seeders (complete peers) reported by the most recent scrape.
+    SCRAPE_COMPLETE, i64, "scrape_complete");
+t_op_const!(
+    /// Get the number of leechers (incomplete peers) reported by the most recent scrape.
+    SCRAPE_INCOMPLETE, i64, "scrape_incomplete");
+t_op_const!(
+    /// Get the number of completed downloads reported by the most recent scrape.
+    SCRAPE_DOWNLOADED, i64, "scrape_downloaded");
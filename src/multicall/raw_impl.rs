@@ -6,15 +6,33 @@ fields across a single XMLRPC call.  The query results are nicely typed.
 
 #![allow(dead_code)]
 
-use crate::{value_conversion, Result, Server};
+use crate::multicall::{system::SystemMultiCall, Order, Row};
+use std::cmp::Ordering;
+use std::sync::Arc;
+use crate::{quote_command_arg, value_conversion::{self, TryFromValue}, CallContext, Error, Result,
+            Server};
 use xmlrpc::{Request, Value};
 
+#[derive(Clone)]
 pub(super) struct MultiBuilderInternal {
     server: Server,
     multicall: String,
     call_target: Value,
     call_filter: Value,
     args: Vec<Value>,
+    // Setter commands and their values, run against each target after the getter columns.  Their
+    // results are not returned.
+    setters: Vec<(String, Value)>,
+    // If set, the query runs over exactly these targets, via system.multicall, rather than via
+    // the `multicall` command.
+    targets: Option<Vec<Value>>,
+    // If set, the query is split into system.multicall batches of this many targets.
+    chunk_size: Option<usize>,
+    // Client-side post-processing of the resulting rows.
+    sort: Option<(usize, Order)>,
+    limit: Option<usize>,
+    // If set, check that the server supports each command before running the query.
+    validate: bool,
 }
 
 impl MultiBuilderInternal {
@@ -25,6 +43,84 @@ impl MultiBuilderInternal {
             call_target,
             call_filter,
             args: Vec::new(),
+            setters: Vec::new(),
+            targets: None,
+            chunk_size: None,
+            sort: None,
+            limit: None,
+            validate: false,
+        }
+    }
+
+    fn with_targets(server: &Server, targets: Vec<Value>) -> Self {
+        Self {
+            server: server.clone(),
+            multicall: String::new(),
+            call_target: Value::from(""),
+            call_filter: Value::from(""),
+            args: Vec::new(),
+            setters: Vec::new(),
+            targets: Some(targets),
+            chunk_size: None,
+            sort: None,
+            limit: None,
+            validate: false,
+        }
+    }
+
+    // A copy of this query, run via `multicall` over `call_target` and `call_filter` instead.
+    pub(crate) fn retargeted(&self, multicall: &str, call_target: &str, call_filter: &str) -> Self {
+        Self {
+            multicall: multicall.to_owned(),
+            call_target: call_target.into(),
+            call_filter: call_filter.into(),
+            targets: None,
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn set_chunk_size(&mut self, chunk_size: usize) {
+        self.chunk_size = Some(chunk_size.max(1));
+    }
+
+    pub(crate) fn set_sort(&mut self, column: usize, order: Order) {
+        self.sort = Some((column, order));
+    }
+
+    pub(crate) fn set_limit(&mut self, limit: usize) {
+        self.limit = Some(limit);
+    }
+
+    pub(crate) fn set_validate(&mut self) {
+        self.validate = true;
+    }
+
+    // Check each column and setter against the server's (cached) supported methods, and report
+    // the first unsupported one.
+    fn check_methods(&self) -> Result<()> {
+        if !self.validate {
+            return Ok(());
+        }
+        let methods = self.server.supported_methods()?;
+        for (i, column) in self.column_names().iter().enumerate() {
+            if !methods.contains(column) {
+                return Err(Error::UnsupportedMethod(format!("column {} ({})", i, column)));
+            }
+        }
+        for (setter, _) in &self.setters {
+            if !methods.contains(setter) {
+                return Err(Error::UnsupportedMethod(format!("setter {}", setter)));
+            }
+        }
+        Ok(())
+    }
+
+    // The call this query is issued as, for error reporting.
+    pub(crate) fn context(&self) -> CallContext {
+        if self.targets.is_some() {
+            CallContext::new("system.multicall", None)
+        } else {
+            CallContext::new(&self.multicall, Some(&self.call_target))
         }
     }
 
@@ -32,20 +128,263 @@ impl MultiBuilderInternal {
         self.args.push(val);
     }
 
-    fn as_request(&self) -> Request {
+    pub(crate) fn push_setter(&mut self, setter: &str, val: Value) {
+        self.setters.push((setter.to_owned(), val));
+    }
+
+    fn as_request(&self) -> Result<Request<'_>> {
         let mut req = Request::new(&self.multicall)
             .arg(self.call_target.clone())
             .arg(self.call_filter.clone());
         for arg in &self.args {
             req = req.arg(arg.clone());
         }
-        req
+        for (setter, val) in &self.setters {
+            req = req.arg(format!("{}={}", setter, encode_setter_arg(val)?));
+        }
+        Ok(req)
     }
 
     pub(crate) fn invoke(&self) -> Result<Vec<Value>> {
-        let list = self.as_request()
-            .call_url(self.server.endpoint())?;
-        Ok(value_conversion::list(&list)?.clone())
+        self.invoke_rows().map_err(|e| e.in_call(self.context()))
+    }
+
+    fn invoke_rows(&self) -> Result<Vec<Value>> {
+        self.check_methods()?;
+        let rows = if let Some(chunk_size) = self.chunk_size {
+            self.invoke_chunked(chunk_size)?
+        } else if let Some(targets) = &self.targets {
+            self.invoke_targets(targets)?
+        } else {
+            let list = self.as_request()?
+                .call_url(self.server.endpoint())?;
+            value_conversion::into_list(list)?
+        };
+        self.post_process(rows)
+    }
+
+    // Run the `multicall` command once per filter (e.g., view), in a single system.multicall,
+    // and return the rows for each filter, in order.
+    pub(crate) fn invoke_filters(&self, filters: &[&str]) -> Result<Vec<Vec<Value>>> {
+        let calls = filters.iter()
+            .map(|filter| (self.call_target.clone(), Value::from(*filter)))
+            .collect::<Vec<_>>();
+        self.invoke_each(&calls).map_err(|e| e.in_call(self.context()))
+    }
+
+    // Run the `multicall` command once per call target (e.g., download), in a single
+    // system.multicall, and return the rows for each call target, in order.
+    pub(crate) fn invoke_call_targets(&self, call_targets: &[&str]) -> Result<Vec<Vec<Value>>> {
+        let calls = call_targets.iter()
+            .map(|call_target| (Value::from(*call_target), self.call_filter.clone()))
+            .collect::<Vec<_>>();
+        self.invoke_each(&calls).map_err(|e| e.in_call(self.context()))
+    }
+
+    fn invoke_each(&self, calls: &[(Value, Value)]) -> Result<Vec<Vec<Value>>> {
+        self.check_methods()?;
+        if calls.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut mc = SystemMultiCall::new();
+        for (call_target, call_filter) in calls {
+            let mut params = vec![call_target.clone(), call_filter.clone()];
+            params.extend(self.args.iter().cloned());
+            for (setter, val) in &self.setters {
+                params.push(Value::from(format!("{}={}", setter, encode_setter_arg(val)?)));
+            }
+            mc.push(&self.multicall, params);
+        }
+        mc.invoke(&self.server)?
+            .into_iter()
+            .map(|list| self.post_process(value_conversion::into_list(list)?))
+            .collect()
+    }
+
+    // Strip setter results, then sort and truncate rows as requested.
+    fn post_process(&self, rows: Vec<Value>) -> Result<Vec<Value>> {
+        let mut rows = if self.setters.is_empty() {
+            rows
+        } else {
+            // Drop the (uninteresting) results of setters from each row.
+            rows.into_iter()
+                .map(|row| {
+                    let mut row = value_conversion::into_list(row)?;
+                    row.truncate(self.args.len());
+                    Ok(Value::Array(row))
+                })
+                .collect::<Result<Vec<_>>>()?
+        };
+
+        if let Some((column, order)) = self.sort {
+            if column >= self.args.len() {
+                return Err(Error::UnexpectedStructure(
+                    format!("sort column {} out of range ({} columns)", column, self.args.len())
+                ));
+            }
+            // Check every row up front, so that sorting can borrow keys in place.
+            for row in &rows {
+                if value_conversion::list(row)?.len() <= column {
+                    return Err(Error::UnexpectedStructure(
+                        format!("row missing sort column {} ({:?})", column, row)
+                    ));
+                }
+            }
+            rows.sort_by(|a, b| match (sort_key(a, column), sort_key(b, column), order) {
+                (Some(a), Some(b), Order::Ascending) => compare_values(a, b),
+                (Some(a), Some(b), Order::Descending) => compare_values(b, a),
+                _ => Ordering::Equal,
+            });
+        }
+        if let Some(limit) = self.limit {
+            rows.truncate(limit);
+        }
+        Ok(rows)
+    }
+
+    // The accessor names of the columns, without the "=arguments" suffix.
+    pub(crate) fn column_names(&self) -> Vec<String> {
+        self.args.iter()
+            .map(|arg| {
+                let arg = value_conversion::string(arg).unwrap_or_default();
+                arg.split_once('=').map_or(arg, |(method, _)| method).to_owned()
+            })
+            .collect()
+    }
+
+    pub(crate) fn invoke_named(&self) -> Result<Vec<Row>> {
+        let columns: Arc<[String]> = self.column_names().into();
+        self.invoke()?
+            .into_iter()
+            .map(|row| {
+                let row = value_conversion::into_list(row)?;
+                if row.len() != columns.len() {
+                    return Err(Error::UnexpectedStructure(
+                        format!("row missing columns ({:?})", row)
+                    ));
+                }
+                Ok(Row::new(columns.clone(), row))
+            })
+            .collect::<Result<_>>()
+            .map_err(|e| e.in_call(self.context()))
+    }
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn invoke_into<T: serde::de::DeserializeOwned>(&self) -> Result<Vec<T>> {
+        let columns = self.column_names();
+        self.invoke()?
+            .iter()
+            .map(|row| crate::multicall::de::from_row(&columns, value_conversion::list(row)?))
+            .collect::<Result<_>>()
+            .map_err(|e| e.in_call(self.context()))
+    }
+
+    // List the targets matched by the multicall command (by hash), then query them a batch at a
+    // time.
+    fn invoke_chunked(&self, chunk_size: usize) -> Result<Vec<Value>> {
+        let listed;
+        let targets = match &self.targets {
+            Some(targets) => targets,
+            None => {
+                let list = Request::new(&self.multicall)
+                    .arg(self.call_target.clone())
+                    .arg(self.call_filter.clone())
+                    .arg("d.hash=")
+                    .call_url(self.server.endpoint())?;
+                listed = value_conversion::into_list(list)?
+                    .into_iter()
+                    .map(|row| {
+                        let mut row = value_conversion::into_list(row)?;
+                        if row.len() != 1 {
+                            return Err(Error::UnexpectedStructure(
+                                format!("Got {:?}, expected single-column row", row)
+                            ));
+                        }
+                        Ok(row.remove(0))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                &listed
+            }
+        };
+
+        let mut res = Vec::with_capacity(targets.len());
+        for chunk in targets.chunks(chunk_size) {
+            res.extend(self.invoke_targets(chunk)?);
+        }
+        Ok(res)
+    }
+
+    // Issue every column accessor against every target in one system.multicall, and regroup the
+    // results into the same row structure a multicall command returns.
+    fn invoke_targets(&self, targets: &[Value]) -> Result<Vec<Value>> {
+        let ncolumns = self.args.len() + self.setters.len();
+        if ncolumns == 0 {
+            return Ok(targets.iter().map(|_| Value::Array(Vec::new())).collect());
+        }
+        let mut mc = SystemMultiCall::new();
+        for target in targets {
+            for arg in &self.args {
+                let arg = value_conversion::string(arg)?;
+                // Columns are encoded "command=arguments", as the multicall commands expect.
+                let (method, params) = arg.split_once('=').unwrap_or((arg, ""));
+                let mut call_params = vec![target.clone()];
+                if !params.is_empty() {
+                    call_params.push(Value::from(params));
+                }
+                mc.push(method, call_params);
+            }
+            for (setter, val) in &self.setters {
+                mc.push(setter, vec![target.clone(), val.clone()]);
+            }
+        }
+        // `SystemMultiCall::invoke` checks that there is one result per call.
+        let mut vals = mc.invoke(&self.server)?.into_iter();
+        Ok(targets.iter()
+           .map(|_| Value::Array(vals.by_ref().take(ncolumns).collect()))
+           .collect())
+    }
+}
+
+// Convert the value of the zero-based `column` of a row, attaching the column to any error.
+pub(super) fn convert_column<T: TryFromValue>(val: &Value, column: &mut usize,
+                                              context: &CallContext) -> Result<T> {
+    let res = T::try_from_value(val).map_err(|e| e.in_call(context.with_column(*column)));
+    *column += 1;
+    res
+}
+
+// The value of `column` of a row, if the row is well-formed.
+fn sort_key(row: &Value, column: usize) -> Option<&Value> {
+    match row {
+        Value::Array(row) => row.get(column),
+        _ => None,
+    }
+}
+
+// Order column values of the same type naturally; values of differing types compare equal.
+fn compare_values(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Int(a), Value::Int(b)) => a.cmp(b),
+        (Value::Int64(a), Value::Int64(b)) => a.cmp(b),
+        (Value::Int(a), Value::Int64(b)) => i64::from(*a).cmp(b),
+        (Value::Int64(a), Value::Int(b)) => a.cmp(&i64::from(*b)),
+        (Value::Double(a), Value::Double(b)) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        _ => Ordering::Equal,
+    }
+}
+
+// Encode a setter value as an argument in the "command=arguments" syntax of multicall commands.
+fn encode_setter_arg(val: &Value) -> Result<String> {
+    match val {
+        Value::Int(i) => Ok(i.to_string()),
+        Value::Int64(i) => Ok(i.to_string()),
+        Value::Bool(b) => Ok((*b as i64).to_string()),
+        Value::String(s) => Ok(quote_command_arg(s)),
+        other => Err(Error::UnexpectedStructure(
+            format!("Got {:?}, expected an integer or string setter value", other)
+        )),
     }
 }
 
@@ -97,6 +436,14 @@ impl MultiBuilder {
                                              call_filter.into()),
         }
     }
+
+    /// Start building a query over exactly the objects identified by `targets` (e.g., download
+    /// infohashes for `d.*` queries).  The query is issued as a single `system.multicall`.
+    pub(crate) fn with_targets(server: &Server, targets: Vec<Value>) -> Self {
+        Self {
+            inner: MultiBuilderInternal::with_targets(server, targets),
+        }
+    }
 }
 
 macro_rules! define_builder {
@@ -111,23 +458,89 @@ macro_rules! define_builder {
 
         impl<$($ty: TryFromValue,)* $ty_last: TryFromValue> $name<$($ty,)* $ty_last> {
             pub(crate) fn invoke(&self) -> Result<Vec<($($ty,)* $ty_last,)>> {
-                let list = self.inner.invoke()?;
-                let mut res = Vec::new();
+                self.invoke_iter()?.collect()
+            }
+
+            pub(crate) fn invoke_iter(&self)
+                -> Result<impl Iterator<Item = Result<($($ty,)* $ty_last,)>>> {
+                Ok(Self::convert_rows(self.inner.invoke()?, self.inner.context()))
+            }
 
-                for row in list {
+            pub(crate) fn invoke_retargeted_filters(&self, multicall: &str, call_target: &str,
+                                                    filters: &[&str])
+                -> Result<Vec<Vec<($($ty,)* $ty_last,)>>> {
+                let inner = self.inner.retargeted(multicall, call_target, "");
+                inner.invoke_filters(filters)?
+                    .into_iter()
+                    .map(|list| Self::convert_rows(list, inner.context()).collect())
+                    .collect()
+            }
+
+            pub(crate) fn invoke_retargeted_targets(&self, multicall: &str,
+                                                    call_targets: &[&str], call_filter: &str)
+                -> Result<Vec<Vec<($($ty,)* $ty_last,)>>> {
+                let inner = self.inner.retargeted(multicall, "", call_filter);
+                inner.invoke_call_targets(call_targets)?
+                    .into_iter()
+                    .map(|list| Self::convert_rows(list, inner.context()).collect())
+                    .collect()
+            }
+
+            pub(crate) fn invoke_retargeted(&self, multicall: &str, call_target: &str,
+                                            call_filter: &str)
+                -> Result<Vec<($($ty,)* $ty_last,)>> {
+                let inner = self.inner.retargeted(multicall, call_target, call_filter);
+                Self::convert_rows(inner.invoke()?, inner.context()).collect()
+            }
+
+            fn convert_rows(list: Vec<Value>, context: CallContext)
+                -> impl Iterator<Item = Result<($($ty,)* $ty_last,)>> {
+                list.into_iter().map(move |row| {
                     let row = value_conversion::list(&row)?;
+                    let mut column = 0;
                     // Repurposing (abusing) existing phantom names for temp variables.
                     if let [$($phantoms,)* $phantom_last] = row.as_slice() {
-                        res.push((
-                                $($ty::try_from_value(&$phantoms)?,)*
-                                $ty_last::try_from_value(&$phantom_last)?,
-                            ));
+                        Ok((
+                                $(convert_column::<$ty>(&$phantoms, &mut column, &context)?,)*
+                                convert_column::<$ty_last>(&$phantom_last, &mut column,
+                                                           &context)?,
+                            ))
                     } else {
-                        return Err(Error::UnexpectedStructure(
-                                format!("row missing columns ({:?})", row)));
+                        Err(Error::UnexpectedStructure(
+                                format!("row missing columns ({:?})", row))
+                            .in_call(context.clone()))
                     }
-                }
-                Ok(res)
+                })
+            }
+
+            pub(crate) fn invoke_named(&self) -> Result<Vec<crate::multicall::Row>> {
+                self.inner.invoke_named()
+            }
+
+            #[cfg(feature = "serde")]
+            pub(crate) fn invoke_into<T: serde::de::DeserializeOwned>(&self) -> Result<Vec<T>> {
+                self.inner.invoke_into()
+            }
+
+            pub(crate) fn sort_by_column(mut self, column: usize, order: crate::multicall::Order)
+                -> Self {
+                self.inner.set_sort(column, order);
+                self
+            }
+
+            pub(crate) fn validate(mut self) -> Self {
+                self.inner.set_validate();
+                self
+            }
+
+            pub(crate) fn limit(mut self, limit: usize) -> Self {
+                self.inner.set_limit(limit);
+                self
+            }
+
+            pub(crate) fn set(mut self, setter: &str, val: Value) -> Self {
+                self.inner.push_setter(setter, val);
+                self
             }
         }
 
@@ -0,0 +1,71 @@
+//! Rtorrent multicall results with named columns
+
+use crate::{value_conversion::TryFromValue, Error, Result};
+use std::sync::Arc;
+use xmlrpc::Value;
+
+/// A single row of a multicall result, with columns indexed by accessor name
+///
+/// `Row`s are returned by the `invoke_named()` method of multicall builders.  They are convenient
+/// for generic consumers (e.g., table renderers) that would rather look up columns by name than
+/// by tuple position.
+///
+/// ## Usage
+///
+/// ```no_run
+/// use rtorrent_xmlrpc_bindings as rtorrent;
+/// use rtorrent::multicall::d;
+///
+/// let my_handle = rtorrent::Server::new("http://1.2.3.4/RPC2");
+///
+/// for row in d::MultiBuilder::new(&my_handle, "default")
+///     .call(d::NAME)
+///     .call(d::RATIO)
+///     .invoke_named()? {
+///     println!("{}: {} ratio", row.get::<String>("d.name")?, row.get::<f64>("d.ratio")?);
+/// }
+/// # Ok::<(), rtorrent::Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct Row {
+    columns: Arc<[String]>,
+    values: Vec<Value>,
+}
+
+impl Row {
+    pub(crate) fn new(columns: Arc<[String]>, values: Vec<Value>) -> Self {
+        Self { columns, values }
+    }
+
+    /// Get the accessor names of the columns in this row, in order.
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Get the raw values in this row, in column order.
+    pub fn values(&self) -> &[Value] {
+        &self.values
+    }
+
+    /// Get the raw value of the column named `column` (e.g., `"d.name"`), if present.
+    pub fn value(&self, column: &str) -> Option<&Value> {
+        self.columns.iter()
+            .position(|c| c == column)
+            .map(|i| &self.values[i])
+    }
+
+    /// Get the value of the column named `column` (e.g., `"d.name"`), converted to `T`.
+    pub fn get<T: TryFromValue>(&self, column: &str) -> Result<T> {
+        match self.value(column) {
+            Some(val) => T::try_from_value(val),
+            None => Err(Error::UnexpectedStructure(
+                format!("no column {:?} in row (columns: {:?})", column, self.columns)
+            )),
+        }
+    }
+
+    /// Iterate over the (accessor name, value) pairs in this row, in column order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.columns.iter().map(|c| c.as_str()).zip(self.values.iter())
+    }
+}
@@ -0,0 +1,84 @@
+/*! Rtorrent system.multicall
+
+This module defines the [`SystemMultiCall`] type, which batches several independent XMLRPC calls
+into a single `system.multicall` round trip.
+!*/
+
+use crate::{CallContext, Error, Result, Server};
+use xmlrpc::{Fault, Request, Value};
+use std::collections::BTreeMap;
+
+pub(crate) struct SystemMultiCall {
+    calls: Vec<Value>,
+    // The method and target of each call, for error reporting.
+    contexts: Vec<CallContext>,
+}
+
+impl SystemMultiCall {
+    pub(crate) fn new() -> Self {
+        Self { calls: Vec::new(), contexts: Vec::new() }
+    }
+
+    /// Add a call of `method` with the given `params` to the batch.  Results are returned by
+    /// `invoke` in the order calls were added.
+    pub(crate) fn push(&mut self, method: &str, params: Vec<Value>) {
+        self.contexts.push(CallContext::new(method, params.first()));
+        let mut call = BTreeMap::new();
+        call.insert("methodName".to_owned(), Value::from(method));
+        call.insert("params".to_owned(), Value::Array(params));
+        self.calls.push(Value::Struct(call));
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    /// Run the batch and return the result of each call, in order.  A fault in any individual
+    /// call fails the entire batch.
+    pub(crate) fn invoke(self, server: &Server) -> Result<Vec<Value>> {
+        self.invoke_each(server)?.into_iter().collect()
+    }
+
+    /// Run the batch and return the result of each call, in order.  Unlike `invoke`, a fault in
+    /// an individual call is returned as that call's result; only a failure of the batch as a
+    /// whole is returned as `Err`.
+    pub(crate) fn invoke_each(self, server: &Server) -> Result<Vec<Result<Value>>> {
+        let ncalls = self.calls.len();
+        let res = Request::new("system.multicall")
+            .arg(Value::Array(self.calls))
+            .call_url(server.endpoint())
+            .map_err(|e| Error::from(e).in_call(CallContext::new("system.multicall", None)))?;
+        let res = match res {
+            Value::Array(a) => a,
+            other => return Err(Error::UnexpectedStructure(
+                format!("Got {:?}, expected array", other)
+            )),
+        };
+        if res.len() != ncalls {
+            return Err(Error::UnexpectedStructure(
+                format!("system.multicall returned {} results for {} calls", res.len(), ncalls)
+            ));
+        }
+        Ok(res.into_iter()
+            .zip(self.contexts)
+            .map(|(val, context)| unwrap_result(val).map_err(|e| e.in_call(context)))
+            .collect())
+    }
+}
+
+// Each successful result is wrapped in a single-element array; failures are fault structs.
+fn unwrap_result(val: Value) -> Result<Value> {
+    if let Some(fault) = Fault::from_value(&val) {
+        return Err(Error::Fault(fault));
+    }
+    match val {
+        Value::Array(mut a) if a.len() == 1 => Ok(a.pop().unwrap()),
+        other => Err(Error::UnexpectedStructure(
+            format!("Got {:?}, expected single-element array", other)
+        )),
+    }
+}
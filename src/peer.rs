@@ -6,9 +6,15 @@ This module defines the [`Peer`] type and support code.
 !*/
 
 use crate::macros::*;
-use crate::{Download, Result};
+use crate::multicall::system::SystemMultiCall;
+use crate::{Download, Error, Result, ToValue};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
-use xmlrpc::{Request, Value};
+use xmlrpc::Value;
 
 macro_rules! p_getter {
     ($(#[$meta:meta])* $method: ident, $result: ty) => {
@@ -36,10 +42,133 @@ macro_rules! p_str_getter {
 
 macro_rules! p_bool_setter {
     ($(#[$meta:meta])* $rmethod: ident, $apimethod: ident) => {
-        prim_setter!($(#[$meta])* "p.", $rmethod, $apimethod, bool);
+        $(#[$meta])*
+        pub fn $rmethod(&self, new: bool) -> Result<()> {
+            // rtorrent's peer setters take an integer value.
+            $crate::call_method(self.endpoint(), concat!("p.", stringify!($apimethod), ".set"),
+                                vec![Value::from(self), Value::from(new as i64)])
+        }
     }
 }
 
+/// A summary of commonly displayed information about a [`Peer`], as returned by
+/// [`Download::peer_summaries`]
+///
+/// [`Peer`]: crate::Peer
+/// [`Download::peer_summaries`]: crate::Download::peer_summaries
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug)]
+pub struct PeerSummary {
+    /// rtorrent's identifier for the peer.
+    pub id: String,
+    /// The remote address and port of the connection to the peer.
+    pub addr: SocketAddr,
+    /// The parsed client version of the peer, or `"Unknown"`.
+    pub client_version: String,
+    /// The download rate from this peer, in bytes/second.
+    pub down_rate: i64,
+    /// The upload rate to this peer, in bytes/second.
+    pub up_rate: i64,
+    /// Total bytes downloaded from this peer.
+    pub down_total: i64,
+    /// Total bytes uploaded to this peer.
+    pub up_total: i64,
+    /// The percent of the download the peer reports it has completed.
+    pub completed_percent: i64,
+    /// Is the connection to this peer encrypted?
+    pub encrypted: bool,
+    /// Did the peer initiate this connection?
+    pub incoming: bool,
+    /// Is the peer snubbed?
+    pub snubbed: bool,
+    /// Is the peer banned?
+    pub banned: bool,
+}
+
+// The getters `PeerSummary::from_values` expects, in order.
+pub(crate) const SUMMARY_METHODS: &[&str] = &["p.id", "p.address", "p.port", "p.client_version",
+                                              "p.down_rate", "p.up_rate", "p.down_total",
+                                              "p.up_total", "p.completed_percent",
+                                              "p.is_encrypted", "p.is_incoming", "p.snubbed",
+                                              "p.banned"];
+
+impl PeerSummary {
+    pub(crate) fn from_values(vals: &[Value]) -> Result<Self> {
+        if vals.len() != SUMMARY_METHODS.len() {
+            return Err(Error::UnexpectedStructure(
+                format!("peer summary row missing columns ({:?})", vals)
+            ));
+        }
+        Ok(Self {
+            id: String::try_from_value(&vals[0])?,
+            addr: parse_socket_addr(&vals[1], i64::try_from_value(&vals[2])?)?,
+            client_version: String::try_from_value(&vals[3])?,
+            down_rate: i64::try_from_value(&vals[4])?,
+            up_rate: i64::try_from_value(&vals[5])?,
+            down_total: i64::try_from_value(&vals[6])?,
+            up_total: i64::try_from_value(&vals[7])?,
+            completed_percent: i64::try_from_value(&vals[8])?,
+            encrypted: bool::try_from_value(&vals[9])?,
+            incoming: bool::try_from_value(&vals[10])?,
+            snubbed: bool::try_from_value(&vals[11])?,
+            banned: bool::try_from_value(&vals[12])?,
+        })
+    }
+}
+
+/// Aggregate statistics over the connected peers of a [`Download`], as returned by
+/// [`Download::swarm_overview`]
+///
+/// [`Download`]: crate::Download
+/// [`Download::swarm_overview`]: crate::Download::swarm_overview
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, Default)]
+pub struct SwarmOverview {
+    /// The number of connected peers.
+    pub peers: usize,
+    /// The number of connected peers that have the complete download.
+    pub seeders: usize,
+    /// The mean completion reported by connected peers, in percent.  Zero if there are no peers.
+    pub average_completion: f64,
+    /// The total download rate from all peers, in bytes/second.
+    pub down_rate: i64,
+    /// The total upload rate to all peers, in bytes/second.
+    pub up_rate: i64,
+    /// The number of connected peers running each client version.
+    pub clients: BTreeMap<String, usize>,
+}
+
+impl SwarmOverview {
+    /// Compute the overview from previously fetched peer summaries.
+    pub fn from_summaries(peers: &[PeerSummary]) -> Self {
+        let mut res = Self { peers: peers.len(), ..Self::default() };
+        let mut completion_sum = 0;
+        for peer in peers {
+            if peer.completed_percent >= 100 {
+                res.seeders += 1;
+            }
+            completion_sum += peer.completed_percent;
+            res.down_rate += peer.down_rate;
+            res.up_rate += peer.up_rate;
+            *res.clients.entry(peer.client_version.clone()).or_insert(0) += 1;
+        }
+        if !peers.is_empty() {
+            res.average_completion = completion_sum as f64 / peers.len() as f64;
+        }
+        res
+    }
+}
+
+fn parse_socket_addr(addr: &Value, port: i64) -> Result<SocketAddr> {
+    let ip = IpAddr::try_from_value(addr)?;
+    if !(0..=u16::MAX as i64).contains(&port) {
+        return Err(Error::UnexpectedStructure(
+            format!("Got {}, expected a port number", port)
+        ));
+    }
+    Ok(SocketAddr::new(ip, port as u16))
+}
+
 #[derive(Debug)]
 pub(crate) struct PeerInner {
     peer_sha1_hex: String,
@@ -99,6 +228,20 @@ impl Peer {
         Self { inner: Arc::new(PeerInner { download, peer_sha1_hex: peerhash.to_owned(), }) }
     }
 
+    /// Construct a Peer representing the peer with rtorrent identifier `peer_id` (as returned
+    /// by `p.id`, e.g., from a [`p::ID`] multicall column) in `download`.  This constructor
+    /// does not validate that the peer is connected.
+    ///
+    /// [`p::ID`]: crate::multicall::p::ID
+    pub fn from_id(download: &Download, peer_id: &str) -> Self {
+        Self::new(download.clone(), peer_id)
+    }
+
+    /// Get rtorrent's identifier for this peer.
+    pub fn id(&self) -> &str {
+        &self.inner.peer_sha1_hex
+    }
+
     #[inline]
     pub(crate) fn endpoint(&self) -> &str {
         self.inner.download.endpoint()
@@ -107,12 +250,42 @@ impl Peer {
     p_str_getter!(
         /// Get the IP address of the peer.
         address);
+
+    /// Get the remote address and port of the connection to this peer, in a single round trip.
+    pub fn socket_addr(&self) -> Result<SocketAddr> {
+        let mut mc = SystemMultiCall::new();
+        mc.push("p.address", vec![Value::from(self)]);
+        mc.push("p.port", vec![Value::from(self)]);
+        let vals = mc.invoke(self.inner.download.server())?;
+        parse_socket_addr(&vals[0], i64::try_from_value(&vals[1])?)
+    }
     p_bool_getter!(
         /// Is the peer banned, e.g., for sending "too much" corrupt data?
         banned);
     p_bool_setter!(
-        /// Ban the peer.
+        /// Ban (`true`) or un-ban (`false`) the peer.
         set_banned, banned);
+
+    /// Ban the peer, and add its address to rtorrent's IP filter so that it is also refused on
+    /// reconnect (until the filter is reloaded or rtorrent restarts).
+    ///
+    /// rtorrent's filter (`ipv4_filter`) only supports IPv4 addresses; IPv6 peers are banned
+    /// as with [`set_banned`], without a filter entry.
+    ///
+    /// [`set_banned`]: crate::Peer::set_banned
+    pub fn ban_persistent(&self) -> Result<()> {
+        let addr = self.socket_addr()?.ip();
+        let mut mc = SystemMultiCall::new();
+        mc.push("p.banned.set", vec![Value::from(self), Value::from(1)]);
+        if addr.is_ipv4() {
+            mc.push("ipv4_filter.add_address",
+                    vec!["".into(), addr.to_string().into(), "unwanted".into()]);
+        }
+        for val in mc.invoke(self.inner.download.server())? {
+            <() as TryFromValue>::try_from_value(&val)?;
+        }
+        Ok(())
+    }
     p_str_getter!(
         /// Get the parsed client version of the peer, if it is a client rtorrent recognizes.
         /// Otherwise, `"Unknown"` is returned.
@@ -156,7 +329,7 @@ impl Peer {
         /// Is the peer snubbed?
         snubbed);
     p_bool_setter!(
-        /// Snub the peer.
+        /// Snub (`true`) or un-snub (`false`) the peer.
         set_snubbed, snubbed);
     p_int_getter!(
         /// The upload rate to this peer, in bytes/second.
@@ -169,8 +342,54 @@ impl Peer {
 unsafe impl Send for Peer {}
 unsafe impl Sync for Peer {}
 
+/// Displays the peer as rtorrent's identifier for it (e.g., `"<infohash>:p<peer id>"`).  See
+/// [`Peer::socket_addr`] for its address.
+///
+/// [`Peer::socket_addr`]: crate::Peer::socket_addr
+impl fmt::Display for Peer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:p{}", self.inner.download.sha1_hex(), self.inner.peer_sha1_hex)
+    }
+}
+
+// Peers are identified by their download and peer ID.
+impl PartialEq for Peer {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner.download == other.inner.download
+            && self.inner.peer_sha1_hex == other.inner.peer_sha1_hex
+    }
+}
+
+impl Eq for Peer {}
+
+impl Hash for Peer {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner.download.hash(state);
+        self.inner.peer_sha1_hex.hash(state);
+    }
+}
+
+impl PartialOrd for Peer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Peer {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.inner.download, &self.inner.peer_sha1_hex)
+            .cmp(&(&other.inner.download, &other.inner.peer_sha1_hex))
+    }
+}
+
 impl From<&Peer> for Value {
     fn from(peer: &Peer) -> Self {
         Value::String(format!("{}:p{}", &peer.inner.download.sha1_hex(), peer.inner.peer_sha1_hex))
     }
 }
+
+impl ToValue for Peer {
+    fn to_value(&self) -> Value {
+        Value::from(self)
+    }
+}
@@ -0,0 +1,293 @@
+/*! Seeding policy enforcement
+
+This module defines the [`SeedingPolicy`] type and support code.
+
+[`SeedingPolicy`]: crate::SeedingPolicy
+!*/
+
+use crate::download::{data_path, DATA_PATH_METHODS};
+use crate::multicall::{d, system::SystemMultiCall};
+use crate::{tracker, Download, DownloadStatus, DownloadSummary, Result, Server};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use xmlrpc::Value;
+
+/// What a [`SeedingPolicy`] does with a download that has seeded enough
+///
+/// [`SeedingPolicy`]: crate::SeedingPolicy
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SeedingAction {
+    /// Stop the download (see [`Download::stop`]).  Downloads that are already stopped are left
+    /// alone.
+    ///
+    /// [`Download::stop`]: crate::Download::stop
+    Stop,
+    /// Remove the download from rtorrent, keeping its data (see [`Download::remove`]).
+    ///
+    /// [`Download::remove`]: crate::Download::remove
+    Erase,
+    /// Remove the download and delete its data from the rtorrent host (see
+    /// [`Download::erase_with_data`]).
+    ///
+    /// [`Download::erase_with_data`]: crate::Download::erase_with_data
+    EraseWithData,
+}
+
+/// The requirements a download must meet before a [`SeedingPolicy`] acts on it
+///
+/// [`SeedingPolicy`]: crate::SeedingPolicy
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SeedingRule {
+    /// If set, the rule only applies to downloads with a tracker at this domain (or a subdomain
+    /// of it).
+    pub tracker: Option<String>,
+    /// The minimum upload/download ratio.
+    pub min_ratio: f64,
+    /// The minimum time since the download finished.
+    pub min_seed_time: Duration,
+    /// What to do once both requirements are met.
+    pub action: SeedingAction,
+}
+
+impl SeedingRule {
+    /// Create a rule applying `action` to downloads that have reached `min_ratio` and have been
+    /// finished for at least `min_seed_time`.
+    pub fn new(min_ratio: f64, min_seed_time: Duration, action: SeedingAction) -> Self {
+        Self { tracker: None, min_ratio, min_seed_time, action }
+    }
+
+    /// Return a rule that only applies to downloads with a tracker at `domain` (or a subdomain of
+    /// it).
+    pub fn tracker(mut self, domain: &str) -> Self {
+        self.tracker = Some(domain.to_ascii_lowercase());
+        self
+    }
+
+    fn applies_to(&self, hosts: &[String]) -> bool {
+        match &self.tracker {
+            None => true,
+            Some(domain) => hosts.iter().any(|host| tracker::host_matches(host, domain)),
+        }
+    }
+}
+
+/// An action taken (or, in a dry run, that would be taken) by a [`SeedingPolicy`]
+///
+/// [`SeedingPolicy`]: crate::SeedingPolicy
+#[derive(Clone, Debug)]
+pub struct SeedingDecision {
+    /// The download.
+    pub download: Download,
+    /// The name of the download.
+    pub name: String,
+    /// The upload/download ratio of the download.
+    pub ratio: f64,
+    /// The time since the download finished.
+    pub seed_time: Duration,
+    /// The action.
+    pub action: SeedingAction,
+}
+
+/// `SeedingPolicy` stops or removes finished downloads once they have seeded enough
+///
+/// Each finished download in the policy's view is matched against the [`SeedingRule`]s in order,
+/// and the first rule that applies to it (by tracker) decides: once the download meets both the
+/// ratio and the seeding time requirements of that rule, the rule's action is applied.  Put
+/// tracker-specific rules before general ones.  Downloads no rule applies to are left alone.
+///
+/// Seeding time is measured from `d.timestamp.finished` to the local clock.  A pass fetches the
+/// state of every download in one multicall (plus one for tracker URLs, if any rule is
+/// tracker-specific) and applies all actions in another.  A dry run only reports what would be
+/// done.
+///
+/// ```no_run
+/// use rtorrent_xmlrpc_bindings as rtorrent;
+/// use rtorrent::{SeedingAction, SeedingPolicy, SeedingRule};
+/// use std::time::Duration;
+///
+/// let my_handle = rtorrent::Server::new("http://1.2.3.4/RPC2");
+///
+/// const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+/// let policy = SeedingPolicy::new()
+///     .rule(SeedingRule::new(1.0, 14 * DAY, SeedingAction::Stop).tracker("private.example.org"))
+///     .rule(SeedingRule::new(2.0, 3 * DAY, SeedingAction::EraseWithData))
+///     .dry_run(true);
+/// for decision in policy.run(&my_handle)? {
+///     println!("Would {:?} {} (ratio {})", decision.action, decision.name, decision.ratio);
+/// }
+/// # Ok::<(), rtorrent::Error>(())
+/// ```
+///
+/// [`SeedingRule`]: crate::SeedingRule
+#[derive(Clone, Debug)]
+pub struct SeedingPolicy {
+    view: String,
+    rules: Vec<SeedingRule>,
+    dry_run: bool,
+}
+
+impl Default for SeedingPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SeedingPolicy {
+    /// Create a policy with no rules, over the downloads in the `"complete"` view.
+    pub fn new() -> Self {
+        Self { view: "complete".to_owned(), rules: Vec::new(), dry_run: false }
+    }
+
+    /// Apply the policy to the downloads in `view`, rather than `"complete"`.  Unfinished
+    /// downloads are never acted on.
+    pub fn view(mut self, view: &str) -> Self {
+        self.view = view.to_owned();
+        self
+    }
+
+    /// Return a policy that, in addition, applies `rule` (after the existing rules).
+    pub fn rule(mut self, rule: SeedingRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Only report actions (if `dry_run` is true), rather than applying them.
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Get the rules of this policy.
+    pub fn rules(&self) -> &[SeedingRule] {
+        &self.rules
+    }
+
+    /// Run one pass of the policy on `server`, and return the actions taken (or that would be
+    /// taken, in a dry run).
+    ///
+    /// The data paths of all downloads to be erased with their data are checked (see
+    /// [`Download::erase_with_data`]) before any action is applied.  Data is only deleted once
+    /// its download has been erased; if any action fails, the data of the downloads that were
+    /// erased is still deleted, and the first error is returned.
+    ///
+    /// [`Download::erase_with_data`]: crate::Download::erase_with_data
+    pub fn run(&self, server: &Server) -> Result<Vec<SeedingDecision>> {
+        if self.rules.is_empty() {
+            return Ok(Vec::new());
+        }
+        let summary_methods = DownloadSummary::methods();
+        let mut builder = d::MultiBuilder::new(server, &self.view)
+            .dynamic()
+            .call("d.hash");
+        for method in &summary_methods {
+            builder.push(method);
+        }
+        for method in DATA_PATH_METHODS {
+            builder.push(method);
+        }
+        let rows = builder.invoke()?;
+        let default_directory = if self.rules.iter()
+            .any(|rule| rule.action == SeedingAction::EraseWithData) {
+            server.default_directory()?
+        } else {
+            String::new()
+        };
+
+        let mut candidates = Vec::new();
+        let paths_start = 1 + summary_methods.len();
+        for row in &rows {
+            let summary = DownloadSummary::from_values(&row[1..paths_start])?;
+            if summary.timestamp_finished > 0 {
+                let download = Download::from_value(server, &row[0])?;
+                candidates.push((download, summary, &row[paths_start..]));
+            }
+        }
+        let hosts = if self.rules.iter().any(|rule| rule.tracker.is_some()) {
+            let downloads = candidates.iter()
+                .map(|(download, _, _)| download.clone())
+                .collect::<Vec<_>>();
+            tracker::tracker_urls(server, &downloads)?
+                .into_iter()
+                .map(|urls| urls.iter().map(|url| tracker::url_host(url)).collect())
+                .collect()
+        } else {
+            vec![Vec::new(); candidates.len()]
+        };
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let mut decisions = Vec::new();
+        let mut mc = SystemMultiCall::new();
+        // The index of the d.erase call of each download to be erased with its data, and the
+        // data path.
+        let mut erasures = Vec::new();
+        for ((download, summary, path_values), hosts) in candidates.into_iter().zip(&hosts) {
+            let rule = match self.rules.iter().find(|rule| rule.applies_to(hosts)) {
+                Some(rule) => rule,
+                None => continue,
+            };
+            let seed_time = Duration::from_secs(
+                now.saturating_sub(summary.timestamp_finished.max(0) as u64)
+            );
+            if summary.ratio < rule.min_ratio || seed_time < rule.min_seed_time {
+                continue;
+            }
+            // Don't stop (or report) a download again on every pass.
+            if rule.action == SeedingAction::Stop && summary.status == DownloadStatus::Stopped {
+                continue;
+            }
+
+            let target = Value::from(&download);
+            match rule.action {
+                SeedingAction::Stop => mc.push("d.stop", vec![target]),
+                SeedingAction::Erase => push_remove(&mut mc, target),
+                SeedingAction::EraseWithData => {
+                    let path = data_path(path_values, &default_directory)?;
+                    push_remove(&mut mc, target);
+                    erasures.push((mc.len() - 1, path.to_string_lossy().into_owned()));
+                }
+            }
+            decisions.push(SeedingDecision {
+                download,
+                name: summary.name,
+                ratio: summary.ratio,
+                seed_time,
+                action: rule.action,
+            });
+        }
+
+        if self.dry_run {
+            return Ok(decisions);
+        }
+        let results = if mc.is_empty() {
+            Vec::new()
+        } else {
+            mc.invoke_each(server)?
+        };
+        let mut rm = SystemMultiCall::new();
+        for (index, base_path) in &erasures {
+            if results[*index].is_ok() {
+                rm.push("execute.throw", ["", "rm", "-rf", "--", base_path].iter()
+                        .map(|arg| Value::from(*arg))
+                        .collect());
+            }
+        }
+        let mut first_error = results.into_iter().find_map(Result::err);
+        if !rm.is_empty() {
+            if let Err(e) = rm.invoke(server) {
+                first_error.get_or_insert(e);
+            }
+        }
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(decisions),
+        }
+    }
+}
+
+// Stop, close, and erase the download (as `Download::remove`).
+fn push_remove(mc: &mut SystemMultiCall, target: Value) {
+    mc.push("d.stop", vec![target.clone()]);
+    mc.push("d.close", vec![target.clone()]);
+    mc.push("d.erase", vec![target]);
+}
@@ -0,0 +1,188 @@
+/*! Filtering downloads
+
+This module defines the [`Query`] type and support code.
+
+[`Query`]: crate::Query
+!*/
+
+use crate::download::label_decode;
+use crate::multicall::{d, Row};
+use crate::{tracker, Download, Result, Server};
+
+#[derive(Clone, Debug)]
+enum Criterion {
+    Label(String),
+    NameContains(String),
+    RatioLt(f64),
+    RatioGe(f64),
+    SizeLt(i64),
+    SizeGe(i64),
+    Complete(bool),
+    TrackerContains(String),
+}
+
+impl Criterion {
+    // The d.multicall2 column this criterion is evaluated against, if any.
+    fn column(&self) -> Option<&'static str> {
+        match self {
+            Criterion::Label(_) => Some("d.custom1"),
+            Criterion::NameContains(_) => Some("d.name"),
+            Criterion::RatioLt(_) | Criterion::RatioGe(_) => Some("d.ratio"),
+            Criterion::SizeLt(_) | Criterion::SizeGe(_) => Some("d.size_bytes"),
+            Criterion::Complete(_) => Some("d.complete"),
+            Criterion::TrackerContains(_) => None,
+        }
+    }
+
+    fn matches_row(&self, row: &Row) -> Result<bool> {
+        Ok(match self {
+            Criterion::Label(label) => label_decode(&row.get::<String>("d.custom1")?) == *label,
+            Criterion::NameContains(s) => row.get::<String>("d.name")?.contains(s.as_str()),
+            Criterion::RatioLt(ratio) => row.get::<f64>("d.ratio")? < *ratio,
+            Criterion::RatioGe(ratio) => row.get::<f64>("d.ratio")? >= *ratio,
+            Criterion::SizeLt(size) => row.get::<i64>("d.size_bytes")? < *size,
+            Criterion::SizeGe(size) => row.get::<i64>("d.size_bytes")? >= *size,
+            Criterion::Complete(complete) => row.get::<bool>("d.complete")? == *complete,
+            Criterion::TrackerContains(_) => true,
+        })
+    }
+}
+
+/// `Query` selects the downloads matching a set of criteria
+///
+/// Criteria are combined with "and."  Running a query issues a single `d.multicall2` over the
+/// view, fetching only the columns the criteria need, and evaluates the criteria client-side.
+/// Tracker criteria require a second round trip, for the downloads that match all other
+/// criteria.
+///
+/// ```no_run
+/// use rtorrent_xmlrpc_bindings as rtorrent;
+/// use rtorrent::Query;
+///
+/// let my_handle = rtorrent::Server::new("http://1.2.3.4/RPC2");
+///
+/// let matching = Query::new()
+///     .label("tv")
+///     .ratio_lt(1.0)
+///     .tracker_contains("example.org")
+///     .run(&my_handle)?;
+/// for download in matching {
+///     println!("{}", download.name()?);
+/// }
+/// # Ok::<(), rtorrent::Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct Query {
+    view: String,
+    criteria: Vec<Criterion>,
+}
+
+impl Default for Query {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Query {
+    /// Create a query matching every download in the `"main"` view.
+    pub fn new() -> Self {
+        Self { view: "main".to_owned(), criteria: Vec::new() }
+    }
+
+    /// Select from the downloads in `view`, rather than `"main"`.
+    pub fn view(mut self, view: &str) -> Self {
+        self.view = view.to_owned();
+        self
+    }
+
+    /// Match downloads with the ruTorrent-style label `label` (see [`Download::label`]).
+    ///
+    /// [`Download::label`]: crate::Download::label
+    pub fn label(self, label: &str) -> Self {
+        self.with(Criterion::Label(label.to_owned()))
+    }
+
+    /// Match downloads whose name contains `s`.
+    pub fn name_contains(self, s: &str) -> Self {
+        self.with(Criterion::NameContains(s.to_owned()))
+    }
+
+    /// Match downloads with an upload/download ratio less than `ratio`.
+    pub fn ratio_lt(self, ratio: f64) -> Self {
+        self.with(Criterion::RatioLt(ratio))
+    }
+
+    /// Match downloads with an upload/download ratio of at least `ratio`.
+    pub fn ratio_ge(self, ratio: f64) -> Self {
+        self.with(Criterion::RatioGe(ratio))
+    }
+
+    /// Match downloads smaller than `size` bytes.
+    pub fn size_lt(self, size: i64) -> Self {
+        self.with(Criterion::SizeLt(size))
+    }
+
+    /// Match downloads of at least `size` bytes.
+    pub fn size_ge(self, size: i64) -> Self {
+        self.with(Criterion::SizeGe(size))
+    }
+
+    /// Match downloads that are (if `complete` is true) or are not finished downloading.
+    pub fn complete(self, complete: bool) -> Self {
+        self.with(Criterion::Complete(complete))
+    }
+
+    /// Match downloads with a tracker whose URL contains `s` (e.g., a domain).
+    pub fn tracker_contains(self, s: &str) -> Self {
+        self.with(Criterion::TrackerContains(s.to_owned()))
+    }
+
+    fn with(mut self, criterion: Criterion) -> Self {
+        self.criteria.push(criterion);
+        self
+    }
+
+    /// Run this query on `server` and return the matching downloads.
+    pub fn run(&self, server: &Server) -> Result<Vec<Download>> {
+        let mut builder = d::MultiBuilder::new(server, &self.view)
+            .dynamic()
+            .call("d.hash");
+        for column in self.criteria.iter().filter_map(Criterion::column) {
+            if !builder.columns().iter().any(|c| c == column) {
+                builder.push(column);
+            }
+        }
+
+        let mut matching = Vec::new();
+        for row in builder.invoke_named()? {
+            let mut matches = true;
+            for criterion in &self.criteria {
+                if !criterion.matches_row(&row)? {
+                    matches = false;
+                    break;
+                }
+            }
+            if matches {
+                matching.push(Download::from_value(server, &row.values()[0])?);
+            }
+        }
+
+        let trackers = self.criteria.iter()
+            .filter_map(|criterion| match criterion {
+                Criterion::TrackerContains(s) => Some(s.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if trackers.is_empty() || matching.is_empty() {
+            return Ok(matching);
+        }
+
+        // Fetch the tracker URLs of the remaining candidates in a single round trip.
+        let urls = tracker::tracker_urls(server, &matching)?;
+        Ok(matching.into_iter()
+           .zip(urls)
+           .filter(|(_, urls)| trackers.iter().all(|s| urls.iter().any(|url| url.contains(s))))
+           .map(|(download, _)| download)
+           .collect())
+    }
+}
@@ -0,0 +1,313 @@
+/*! Aggregate reports over many downloads
+
+This module defines the [`DiskUsageReport`] and [`TrackerHealthReport`] types and support code.
+
+[`DiskUsageReport`]: crate::DiskUsageReport
+[`TrackerHealthReport`]: crate::TrackerHealthReport
+!*/
+
+use crate::download::label_decode;
+use crate::multicall::{d, t};
+use crate::{tracker, Download, Result, Server, TryFromValue};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// How a [`DiskUsageReport`] groups downloads
+///
+/// [`DiskUsageReport`]: crate::DiskUsageReport
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GroupBy {
+    /// By ruTorrent-style label (see [`Download::label`]).
+    ///
+    /// [`Download::label`]: crate::Download::label
+    Label,
+    /// By the views downloads are tagged into (see [`Download::views`]).  A download tagged into
+    /// several views counts toward each of them.
+    ///
+    /// [`Download::views`]: crate::Download::views
+    View,
+    /// By tracker host (e.g., `"tracker.example.org"`).  A download with trackers on several
+    /// hosts counts toward each of them.
+    TrackerDomain,
+}
+
+/// The disk usage of a group of downloads, as part of a [`DiskUsageReport`]
+///
+/// [`DiskUsageReport`]: crate::DiskUsageReport
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DiskUsage {
+    /// The number of downloads.
+    pub downloads: usize,
+    /// The total size of the downloads (bytes).
+    pub size_bytes: i64,
+    /// The total number of completed bytes (i.e., the space used on disk).
+    pub completed_bytes: i64,
+}
+
+impl DiskUsage {
+    fn add(&mut self, size_bytes: i64, completed_bytes: i64) {
+        self.downloads += 1;
+        self.size_bytes += size_bytes;
+        self.completed_bytes += completed_bytes;
+    }
+}
+
+/// Disk usage of the downloads in a view, grouped by label, view, or tracker, as returned by
+/// [`Server::disk_usage`]
+///
+/// Downloads with no label, views, or trackers are grouped under the empty string.
+///
+/// ```no_run
+/// use rtorrent_xmlrpc_bindings as rtorrent;
+/// use rtorrent::GroupBy;
+///
+/// let my_handle = rtorrent::Server::new("http://1.2.3.4/RPC2");
+///
+/// let report = my_handle.disk_usage("main", GroupBy::Label)?;
+/// for (label, usage) in &report.groups {
+///     println!("{}: {} bytes in {} downloads", label, usage.completed_bytes, usage.downloads);
+/// }
+/// println!("Total: {} bytes", report.total.completed_bytes);
+/// # Ok::<(), rtorrent::Error>(())
+/// ```
+///
+/// [`Server::disk_usage`]: crate::Server::disk_usage
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, Default)]
+pub struct DiskUsageReport {
+    /// The usage of each group, by name.
+    pub groups: BTreeMap<String, DiskUsage>,
+    /// The usage of all of the downloads (each counted once).
+    pub total: DiskUsage,
+}
+
+impl DiskUsageReport {
+    pub(crate) fn fetch(server: &Server, view: &str, group_by: GroupBy) -> Result<Self> {
+        let column = match group_by {
+            GroupBy::Label => "d.custom1",
+            GroupBy::View => "d.views",
+            GroupBy::TrackerDomain => "d.hash",
+        };
+        let rows = d::MultiBuilder::new(server, view)
+            .dynamic()
+            .call("d.size_bytes")
+            .call("d.completed_bytes")
+            .call(column)
+            .invoke()?;
+
+        let groups = match group_by {
+            GroupBy::Label => rows.iter()
+                .map(|row| Ok(vec![label_decode(&String::try_from_value(&row[2])?)]))
+                .collect::<Result<Vec<_>>>()?,
+            GroupBy::View => rows.iter()
+                .map(|row| Vec::<String>::try_from_value(&row[2]))
+                .collect::<Result<Vec<_>>>()?,
+            // Tracker URLs are not available from d.multicall2; fetch them separately.
+            GroupBy::TrackerDomain => {
+                let downloads = rows.iter()
+                    .map(|row| Download::from_value(server, &row[2]))
+                    .collect::<Result<Vec<_>>>()?;
+                tracker::tracker_urls(server, &downloads)?
+                    .iter()
+                    .map(|urls| urls.iter()
+                         .map(|url| tracker::url_host(url))
+                         .collect::<BTreeSet<_>>()
+                         .into_iter()
+                         .collect())
+                    .collect()
+            }
+        };
+
+        let mut report = Self::default();
+        for (row, names) in rows.iter().zip(groups) {
+            let size_bytes = i64::try_from_value(&row[0])?;
+            let completed_bytes = i64::try_from_value(&row[1])?;
+            report.total.add(size_bytes, completed_bytes);
+            if names.is_empty() {
+                report.groups.entry(String::new()).or_default().add(size_bytes, completed_bytes);
+            }
+            for name in names {
+                report.groups.entry(name).or_default().add(size_bytes, completed_bytes);
+            }
+        }
+        Ok(report)
+    }
+}
+
+/// The health of a tracker of a download, as classified by [`TrackerHealthReport`]
+///
+/// [`TrackerHealthReport`]: crate::TrackerHealthReport
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum TrackerHealth {
+    /// The most recent announce succeeded.
+    Ok,
+    /// The tracker does not know the torrent (e.g., it was deleted from a private tracker).
+    Unregistered,
+    /// The tracker could not be reached, or did not respond in time.
+    TimedOut,
+    /// The tracker refused the client (e.g., a banned client or IP).
+    Banned,
+    /// Announces are failing for another reason.
+    Failing,
+}
+
+impl TrackerHealth {
+    // Classify a failing tracker by the download's message.
+    fn classify(message: &str) -> Self {
+        let message = message.to_ascii_lowercase();
+        let any = |needles: &[&str]| needles.iter().any(|needle| message.contains(needle));
+        if any(&["unregistered", "not registered", "torrent not found", "unknown torrent",
+                 "torrent does not exist"]) {
+            TrackerHealth::Unregistered
+        } else if any(&["timed out", "timeout", "could not resolve", "couldn't resolve",
+                        "connection refused", "couldn't connect", "could not connect"]) {
+            TrackerHealth::TimedOut
+        } else if any(&["banned", "blacklisted", "not whitelisted", "not allowed"]) {
+            TrackerHealth::Banned
+        } else {
+            TrackerHealth::Failing
+        }
+    }
+}
+
+/// The health of one tracker of one download, as part of a [`TrackerHealthReport`]
+///
+/// [`TrackerHealthReport`]: crate::TrackerHealthReport
+#[derive(Clone, Debug)]
+pub struct TrackerHealthEntry {
+    /// The download.
+    pub download: Download,
+    /// The name of the download.
+    pub name: String,
+    /// The URL of the tracker.
+    pub url: String,
+    /// The classified health of the tracker.
+    pub health: TrackerHealth,
+    /// The tracker error reported for the download (`d.message`), if the tracker is failing.
+    pub message: String,
+}
+
+/// The health of the trackers of the downloads in a view, grouped by tracker host, as returned by
+/// [`Server::tracker_health`]
+///
+/// A tracker is considered failing if its most recent announce failed (`t.failed_counter` is
+/// nonzero).  rtorrent only reports errors per download (`d.message`), so failing trackers are
+/// classified by the download's most recent tracker error; if several trackers of a download are
+/// failing, they share a classification.  Disabled trackers are not included.
+///
+/// ```no_run
+/// use rtorrent_xmlrpc_bindings as rtorrent;
+/// use rtorrent::TrackerHealth;
+///
+/// let my_handle = rtorrent::Server::new("http://1.2.3.4/RPC2");
+///
+/// let report = my_handle.tracker_health("main")?;
+/// for entry in report.with_health(TrackerHealth::Unregistered) {
+///     println!("{} is unregistered at {}", entry.name, entry.url);
+/// }
+/// for (host, entries) in &report.hosts {
+///     let ok = entries.iter().filter(|e| e.health == TrackerHealth::Ok).count();
+///     println!("{}: {}/{} OK", host, ok, entries.len());
+/// }
+/// # Ok::<(), rtorrent::Error>(())
+/// ```
+///
+/// [`Server::tracker_health`]: crate::Server::tracker_health
+#[derive(Clone, Debug, Default)]
+pub struct TrackerHealthReport {
+    /// The health of each (download, tracker) pair, by tracker host.
+    pub hosts: BTreeMap<String, Vec<TrackerHealthEntry>>,
+}
+
+impl TrackerHealthReport {
+    pub(crate) fn fetch(server: &Server, view: &str) -> Result<Self> {
+        let rows = d::MultiBuilder::new(server, view)
+            .dynamic()
+            .call("d.hash")
+            .call("d.name")
+            .call("d.message")
+            .invoke()?;
+        let downloads = rows.iter()
+            .map(|row| Download::from_value(server, &row[0]))
+            .collect::<Result<Vec<_>>>()?;
+
+        // Fetch the trackers of every download in a single round trip.
+        let hashes = downloads.iter().map(Download::sha1_hex).collect::<Vec<_>>();
+        let trackers = t::MultiBuilder::new(server, "")
+            .call(t::URL)
+            .call(t::IS_ENABLED)
+            .call(t::FAILED_COUNTER)
+            .invoke_downloads(&hashes)?;
+
+        let mut report = Self::default();
+        for ((row, download), trackers) in rows.iter().zip(downloads).zip(trackers) {
+            let name = String::try_from_value(&row[1])?;
+            let message = String::try_from_value(&row[2])?;
+            let message = message.strip_prefix("Tracker:").map(str::trim);
+            for (url, enabled, failed) in trackers {
+                if !enabled {
+                    continue;
+                }
+                let (health, message) = match message {
+                    _ if failed == 0 => (TrackerHealth::Ok, String::new()),
+                    Some(message) => (TrackerHealth::classify(message), message.to_owned()),
+                    None => (TrackerHealth::Failing, String::new()),
+                };
+                report.hosts.entry(tracker::url_host(&url)).or_default().push(TrackerHealthEntry {
+                    download: download.clone(),
+                    name: name.clone(),
+                    url,
+                    health,
+                    message,
+                });
+            }
+        }
+        Ok(report)
+    }
+
+    /// Iterate over the entries (of every host) with the given health.
+    pub fn with_health(&self, health: TrackerHealth)
+        -> impl Iterator<Item = &TrackerHealthEntry> + '_ {
+        self.hosts.values()
+            .flatten()
+            .filter(move |entry| entry.health == health)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TrackerHealth;
+
+    #[test]
+    fn classify() {
+        let cases = [
+            ("Unregistered torrent", TrackerHealth::Unregistered),
+            ("Torrent not registered with this tracker", TrackerHealth::Unregistered),
+            ("torrent not found", TrackerHealth::Unregistered),
+            ("Unknown Torrent", TrackerHealth::Unregistered),
+            ("Timeout was reached", TrackerHealth::TimedOut),
+            ("Operation timed out after 30000 milliseconds", TrackerHealth::TimedOut),
+            ("Could not resolve host: tracker.example.org", TrackerHealth::TimedOut),
+            ("Couldn't connect to server", TrackerHealth::TimedOut),
+            ("Connection refused", TrackerHealth::TimedOut),
+            ("Client is banned", TrackerHealth::Banned),
+            ("Your client is not whitelisted", TrackerHealth::Banned),
+            ("BLACKLISTED", TrackerHealth::Banned),
+            ("Passkey is invalid", TrackerHealth::Failing),
+            ("", TrackerHealth::Failing),
+        ];
+        for (message, health) in cases {
+            assert_eq!(TrackerHealth::classify(message), health, "{:?}", message);
+        }
+    }
+
+    #[test]
+    fn classify_precedence() {
+        // A message matching several classes takes the first, in the order checked.
+        assert_eq!(TrackerHealth::classify("unregistered torrent (banned client)"),
+                   TrackerHealth::Unregistered);
+        assert_eq!(TrackerHealth::classify("timed out; not allowed"), TrackerHealth::TimedOut);
+    }
+}
@@ -0,0 +1,407 @@
+/*! Rules for labeling and organizing downloads
+
+This module defines the [`RuleSet`] type, which applies [`Action`]s (e.g., setting a label) to
+downloads matching [`Condition`]s (e.g., a tracker domain).  A `RuleSet` can be applied to any
+downloads, or to newly added downloads as they appear via a [`Watcher`].
+
+```no_run
+use rtorrent_xmlrpc_bindings as rtorrent;
+use rtorrent::rules::{Action, Condition, Rule, RuleSet};
+use rtorrent::watch::Watcher;
+use std::time::Duration;
+
+let my_handle = rtorrent::Server::new("http://1.2.3.4/RPC2");
+
+let rules = RuleSet::new()
+    .rule(Rule::new()
+          .when(Condition::TrackerDomain("tv.example.org".to_owned()))
+          .then(Action::SetLabel("tv".to_owned()))
+          .then(Action::AddView("tv".to_owned())))
+    .rule(Rule::new()
+          .when(Condition::NameMatches("*.iso".to_owned()))
+          .when(Condition::SizeAtLeast(1 << 30))
+          .then(Action::SetThrottle("slow".to_owned())));
+
+let mut watcher = Watcher::new(&my_handle, "main");
+rules.watch(&mut watcher, Duration::from_secs(10))?;
+# Ok::<(), rtorrent::Error>(())
+```
+
+[`Action`]: crate::rules::Action
+[`Condition`]: crate::rules::Condition
+[`RuleSet`]: crate::rules::RuleSet
+[`Watcher`]: crate::watch::Watcher
+!*/
+
+use crate::download::label_encode;
+use crate::multicall::{d, system::SystemMultiCall};
+use crate::watch::{Event, Watcher};
+use crate::{tracker, value_conversion, Download, DownloadStatus, DownloadSummary, Result, Server};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use xmlrpc::Value;
+
+/// A test of a download, as part of a [`Rule`]
+///
+/// [`Rule`]: crate::rules::Rule
+#[derive(Clone)]
+pub enum Condition {
+    /// Some tracker of the download is at this domain (or a subdomain of it).
+    TrackerDomain(String),
+    /// The name of the download matches this pattern, where `*` matches any sequence of
+    /// characters and `?` matches any single character.
+    NameMatches(String),
+    /// The download is at least this large (bytes).
+    SizeAtLeast(i64),
+    /// The download is smaller than this (bytes).
+    SizeBelow(i64),
+    /// The download is in this view (e.g., a view filtered by a watch directory's custom field).
+    InView(String),
+    /// An arbitrary test of the download's summary (e.g., a regular expression match on its
+    /// name).
+    Custom(Arc<dyn Fn(&DownloadSummary) -> bool + Send + Sync>),
+}
+
+impl fmt::Debug for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Condition::TrackerDomain(domain) => {
+                f.debug_tuple("TrackerDomain").field(domain).finish()
+            }
+            Condition::NameMatches(pattern) => f.debug_tuple("NameMatches").field(pattern).finish(),
+            Condition::SizeAtLeast(size) => f.debug_tuple("SizeAtLeast").field(size).finish(),
+            Condition::SizeBelow(size) => f.debug_tuple("SizeBelow").field(size).finish(),
+            Condition::InView(view) => f.debug_tuple("InView").field(view).finish(),
+            Condition::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// A change applied to matching downloads, as part of a [`Rule`]
+///
+/// [`Rule`]: crate::rules::Rule
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// Set the ruTorrent-style label (`custom1`; see [`Download::set_label`]).
+    ///
+    /// [`Download::set_label`]: crate::Download::set_label
+    SetLabel(String),
+    /// Tag the download into a view (see [`Download::add_view`]).
+    ///
+    /// [`Download::add_view`]: crate::Download::add_view
+    AddView(String),
+    /// Assign the download to a throttle group (see [`Download::set_throttle_name`]).  Started
+    /// downloads are stopped while the group is changed, and then restarted.
+    ///
+    /// [`Download::set_throttle_name`]: crate::Download::set_throttle_name
+    SetThrottle(String),
+}
+
+/// A set of [`Condition`]s, all of which must hold for a download, and the [`Action`]s to apply
+/// to it if they do
+///
+/// [`Action`]: crate::rules::Action
+/// [`Condition`]: crate::rules::Condition
+#[derive(Clone, Debug, Default)]
+pub struct Rule {
+    conditions: Vec<Condition>,
+    actions: Vec<Action>,
+}
+
+impl Rule {
+    /// Create a rule with no conditions (matching every download) and no actions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return a rule that, in addition, requires `condition`.
+    pub fn when(mut self, condition: Condition) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    /// Return a rule that, in addition, applies `action`.
+    pub fn then(mut self, action: Action) -> Self {
+        self.actions.push(action);
+        self
+    }
+
+    /// Get the conditions of this rule.
+    pub fn conditions(&self) -> &[Condition] {
+        &self.conditions
+    }
+
+    /// Get the actions of this rule.
+    pub fn actions(&self) -> &[Action] {
+        &self.actions
+    }
+}
+
+// What conditions are evaluated against, for one download.
+struct Facts<'a> {
+    summary: &'a DownloadSummary,
+    hosts: &'a [String],
+    views: &'a BTreeMap<&'a str, BTreeSet<String>>,
+    hash: &'a str,
+}
+
+impl Condition {
+    fn matches(&self, facts: &Facts) -> bool {
+        match self {
+            Condition::TrackerDomain(domain) => {
+                facts.hosts.iter().any(|host| tracker::host_matches(host, domain))
+            }
+            Condition::NameMatches(pattern) => glob_match(pattern, &facts.summary.name),
+            Condition::SizeAtLeast(size) => facts.summary.size_bytes >= *size,
+            Condition::SizeBelow(size) => facts.summary.size_bytes < *size,
+            Condition::InView(view) => {
+                matches!(facts.views.get(view.as_str()),
+                         Some(hashes) if hashes.contains(facts.hash))
+            }
+            Condition::Custom(test) => test(facts.summary),
+        }
+    }
+}
+
+/// `RuleSet` applies [`Rule`]s to downloads
+///
+/// Every rule is evaluated against every download, and the actions of all matching rules are
+/// applied, in order.
+///
+/// [`Rule`]: crate::rules::Rule
+#[derive(Clone, Debug, Default)]
+pub struct RuleSet {
+    rules: Vec<Rule>,
+}
+
+impl RuleSet {
+    /// Create an empty rule set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return a rule set that, in addition, applies `rule`.
+    pub fn rule(mut self, rule: Rule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Get the rules of this set.
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Apply the rules to `downloads` (which must all belong to `server`).
+    ///
+    /// Returns each download that matched some rule, with the actions applied to it.  The facts
+    /// rules need are gathered in a few multicalls, regardless of the number of downloads.
+    pub fn apply(&self, server: &Server, downloads: &[Download])
+        -> Result<Vec<(Download, Vec<Action>)>> {
+        if downloads.is_empty() || self.rules.is_empty() {
+            return Ok(Vec::new());
+        }
+        let hashes = downloads.iter().map(|dl| dl.sha1_hex()).collect::<Vec<_>>();
+        let summaries = DownloadSummary::fetch(server,
+                                               d::MultiBuilder::for_hashes(server, &hashes))?;
+
+        let conditions = || self.rules.iter().flat_map(|rule| &rule.conditions);
+        let hosts = if conditions().any(|c| matches!(c, Condition::TrackerDomain(_))) {
+            tracker::tracker_urls(server, downloads)?
+                .into_iter()
+                .map(|urls| urls.iter().map(|url| tracker::url_host(url)).collect())
+                .collect()
+        } else {
+            vec![Vec::new(); downloads.len()]
+        };
+        let mut views = BTreeMap::new();
+        for condition in conditions() {
+            if let Condition::InView(view) = condition {
+                if !views.contains_key(view.as_str()) {
+                    views.insert(view.as_str(), view_hashes(server, view)?);
+                }
+            }
+        }
+
+        let mut applied = Vec::new();
+        let mut mc = SystemMultiCall::new();
+        for ((download, summary), hosts) in summaries.into_iter().zip(&hosts) {
+            let facts = Facts {
+                summary: &summary,
+                hosts,
+                views: &views,
+                hash: download.sha1_hex(),
+            };
+            let actions = self.rules.iter()
+                .filter(|rule| rule.conditions.iter().all(|c| c.matches(&facts)))
+                .flat_map(|rule| rule.actions.iter().cloned())
+                .collect::<Vec<_>>();
+            if actions.is_empty() {
+                continue;
+            }
+            for action in &actions {
+                push_action(&mut mc, &download, &summary, action);
+            }
+            applied.push((download, actions));
+        }
+        if !mc.is_empty() {
+            mc.invoke(server)?;
+        }
+        Ok(applied)
+    }
+
+    /// Poll `watcher` every `interval`, and apply the rules to each download it reports as added.
+    ///
+    /// Downloads erased before their rules are applied are skipped.  Returns the first error
+    /// encountered.
+    pub fn watch(&self, watcher: &mut Watcher, interval: Duration) -> Result<()> {
+        loop {
+            let added = watcher.poll()?
+                .into_iter()
+                .filter_map(|event| match event {
+                    Event::DownloadAdded(download) => Some(download),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            if let Some(first) = added.first() {
+                let server = first.server().clone();
+                self.apply_existing(&server, added)?;
+            }
+            thread::sleep(interval);
+        }
+    }
+
+    // Apply the rules to those of `downloads` that still exist.  `apply` fails as a whole if any
+    // download was erased, so drop the erased ones and retry.
+    fn apply_existing(&self, server: &Server, mut downloads: Vec<Download>) -> Result<()> {
+        loop {
+            match self.apply(server, &downloads) {
+                Err(e) if e.is_not_found() => {
+                    let present = view_hashes(server, "main")?;
+                    let count = downloads.len();
+                    downloads.retain(|download| present.contains(download.sha1_hex()));
+                    if downloads.len() == count {
+                        return Err(e);
+                    }
+                }
+                result => return result.map(drop),
+            }
+        }
+    }
+}
+
+fn push_action(mc: &mut SystemMultiCall, download: &Download, summary: &DownloadSummary,
+               action: &Action) {
+    let target = Value::from(download);
+    match action {
+        Action::SetLabel(label) => {
+            mc.push("d.custom1.set", vec![target, Value::from(label_encode(label))]);
+        }
+        Action::AddView(view) => {
+            mc.push("d.views.push_back_unique",
+                    vec![target.clone(), Value::from(view.as_str())]);
+            mc.push("view.set_visible", vec![target, Value::from(view.as_str())]);
+        }
+        Action::SetThrottle(name) => {
+            let started = summary.status != DownloadStatus::Stopped;
+            if started {
+                mc.push("d.stop", vec![target.clone()]);
+            }
+            mc.push("d.throttle_name.set", vec![target.clone(), Value::from(name.as_str())]);
+            if started {
+                mc.push("d.start", vec![target]);
+            }
+        }
+    }
+}
+
+// The infohashes of the downloads in `view`.
+fn view_hashes(server: &Server, view: &str) -> Result<BTreeSet<String>> {
+    d::MultiBuilder::new(server, view)
+        .dynamic()
+        .call("d.hash")
+        .invoke()?
+        .iter()
+        .map(|row| Ok(value_conversion::string(&row[0])?.to_owned()))
+        .collect()
+}
+
+// Match `s` against a pattern of literal characters and the wildcards `*` (any sequence) and `?`
+// (any single character).
+fn glob_match(pattern: &str, s: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let s = s.chars().collect::<Vec<_>>();
+    let (mut p, mut i) = (0, 0);
+    // The position of the last `*` seen, and the position in `s` it is currently matched up to.
+    let mut backtrack = None;
+    while i < s.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == s[i]) {
+            p += 1;
+            i += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, i));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            i = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::glob_match;
+
+    #[test]
+    fn glob_literal() {
+        assert!(glob_match("", ""));
+        assert!(glob_match("abc", "abc"));
+        assert!(!glob_match("abc", "abd"));
+        assert!(!glob_match("abc", "ab"));
+        assert!(!glob_match("ab", "abc"));
+        assert!(!glob_match("", "a"));
+        // Matching is case-sensitive.
+        assert!(!glob_match("ABC", "abc"));
+    }
+
+    #[test]
+    fn glob_question_mark() {
+        assert!(glob_match("a?c", "abc"));
+        assert!(!glob_match("a?c", "ac"));
+        assert!(!glob_match("a?c", "abbc"));
+        assert!(glob_match("???", "abc"));
+        // `?` matches a character, not a byte.
+        assert!(glob_match("S?ries", "Séries"));
+    }
+
+    #[test]
+    fn glob_star() {
+        assert!(glob_match("*", ""));
+        assert!(glob_match("*", "anything"));
+        assert!(glob_match("**", "a"));
+        assert!(glob_match("*.mkv", "movie.mkv"));
+        assert!(!glob_match("*.mkv", "movie.mkv.part"));
+        assert!(glob_match("*sample*", "Movie.Sample.sample.mkv"));
+        assert!(glob_match("a*", "a"));
+        assert!(!glob_match("a*", "ba"));
+    }
+
+    #[test]
+    fn glob_backtracking() {
+        // The first candidate match of each `*` is wrong.
+        assert!(glob_match("*a", "baa"));
+        assert!(glob_match("a*b*c", "abXbXc"));
+        assert!(glob_match("*ab*ab", "abXabab"));
+        assert!(glob_match("*?b", "abbb"));
+        assert!(glob_match("a*?*c", "abc"));
+        assert!(!glob_match("a*?*c", "ac"));
+        assert!(!glob_match("*a*b", "bXa"));
+        assert!(glob_match("*.*.*", "a.b.c.d"));
+    }
+}
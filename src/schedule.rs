@@ -0,0 +1,212 @@
+/*! Time-based bandwidth limits
+
+This module defines the [`ThrottleSchedule`] type and support code.
+
+[`ThrottleSchedule`]: crate::ThrottleSchedule
+!*/
+
+use crate::{ErrorKind, Result, Server};
+
+/// The throttle a [`ThrottleRule`] limits
+///
+/// [`ThrottleRule`]: crate::ThrottleRule
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ThrottleTarget {
+    /// The global throttle, limiting all downloads.
+    Global,
+    /// The named throttle group (see [`Download::set_throttle_name`]).
+    ///
+    /// [`Download::set_throttle_name`]: crate::Download::set_throttle_name
+    Group(String),
+}
+
+/// A change of throttle limits at a time of day, as part of a [`ThrottleSchedule`]
+///
+/// [`ThrottleSchedule`]: crate::ThrottleSchedule
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ThrottleRule {
+    /// The hour of the day (0-23, in the rtorrent host's time zone) the limits take effect.
+    pub hour: u8,
+    /// The minute of the hour (0-59) the limits take effect.
+    pub minute: u8,
+    /// The throttle to limit.
+    pub target: ThrottleTarget,
+    /// The download rate limit (bytes/s).  Zero is unlimited.
+    pub down_rate: i64,
+    /// The upload rate limit (bytes/s).  Zero is unlimited.
+    pub up_rate: i64,
+}
+
+impl ThrottleRule {
+    // The (method, rate) pairs that apply this rule's download and upload limits.
+    fn settings(&self) -> [(&'static str, i64); 2] {
+        match self.target {
+            ThrottleTarget::Global => [
+                ("throttle.global_down.max_rate.set", self.down_rate),
+                ("throttle.global_up.max_rate.set", self.up_rate),
+            ],
+            // Throttle group limits are in KiB/s; round up so that small limits do not become
+            // "unlimited."
+            ThrottleTarget::Group(_) => [
+                ("throttle.down", (self.down_rate + 1023) / 1024),
+                ("throttle.up", (self.up_rate + 1023) / 1024),
+            ],
+        }
+    }
+
+    // The rtorrent commands that apply this rule's download and upload limits.
+    fn commands(&self) -> Vec<String> {
+        self.settings().iter()
+            .map(|(method, rate)| match &self.target {
+                ThrottleTarget::Global => format!("{}={}", method, rate),
+                ThrottleTarget::Group(name) => format!("{}={},{}", method, name, rate),
+            })
+            .collect()
+    }
+
+    /// Apply this rule's limits immediately.
+    pub fn apply(&self, server: &Server) -> Result<()> {
+        for (method, rate) in &self.settings() {
+            match &self.target {
+                ThrottleTarget::Global => server.call::<()>(method, &[&"", rate])?,
+                ThrottleTarget::Group(name) => server.call::<()>(method, &[&"", name, rate])?,
+            }
+        }
+        Ok(())
+    }
+
+    fn time(&self) -> String {
+        format!("{:02}:{:02}:00", self.hour, self.minute)
+    }
+}
+
+/// `ThrottleSchedule` changes throttle limits at fixed times of day
+///
+/// Rules are installed on the rtorrent host as daily `schedule2` entries, so they keep applying
+/// even while no client is connected.  The entries are named after the schedule (e.g.,
+/// `"bandwidth_0_down"`) and can be listed with [`installed`] and removed with [`uninstall`].
+/// rtorrent cannot list `schedule2` entries, so the number of installed rules is recorded on the
+/// host too, in the variable `throttle_schedule.{name}`; a schedule of the same name created
+/// later (e.g., by another process) can list and remove them.
+///
+/// Scheduled limits first take effect at their next scheduled time; use [`ThrottleRule::apply`]
+/// to apply the limits for the current time immediately.
+///
+/// ```no_run
+/// use rtorrent_xmlrpc_bindings as rtorrent;
+/// use rtorrent::{ThrottleSchedule, ThrottleTarget};
+///
+/// let my_handle = rtorrent::Server::new("http://1.2.3.4/RPC2");
+///
+/// // Unlimited at night, capped to 1 MiB/s down and 256 KiB/s up during the day.
+/// let schedule = ThrottleSchedule::new(&my_handle, "bandwidth")
+///     .at(0, 0, ThrottleTarget::Global, 0, 0)
+///     .at(8, 0, ThrottleTarget::Global, 1 << 20, 256 << 10);
+/// schedule.install()?;
+/// println!("Installed {:?}", schedule.installed()?);
+///
+/// // Later:
+/// schedule.uninstall()?;
+/// # Ok::<(), rtorrent::Error>(())
+/// ```
+///
+/// [`installed`]: crate::ThrottleSchedule::installed
+/// [`uninstall`]: crate::ThrottleSchedule::uninstall
+/// [`ThrottleRule::apply`]: crate::ThrottleRule::apply
+#[derive(Clone, Debug)]
+pub struct ThrottleSchedule {
+    server: Server,
+    name: String,
+    rules: Vec<ThrottleRule>,
+}
+
+impl ThrottleSchedule {
+    /// Create an empty schedule named `name` (used as the prefix of its `schedule2` entries).
+    pub fn new(server: &Server, name: &str) -> Self {
+        Self {
+            server: server.clone(),
+            name: name.to_owned(),
+            rules: Vec::new(),
+        }
+    }
+
+    /// Return a schedule that, in addition, limits `target` to `down_rate` and `up_rate`
+    /// (bytes/s; zero is unlimited) daily from `hour`:`minute`.
+    pub fn at(mut self, hour: u8, minute: u8, target: ThrottleTarget, down_rate: i64,
+              up_rate: i64) -> Self {
+        self.rules.push(ThrottleRule { hour, minute, target, down_rate, up_rate });
+        self
+    }
+
+    /// Get the rules of this schedule, in the order they were added.
+    pub fn rules(&self) -> &[ThrottleRule] {
+        &self.rules
+    }
+
+    /// Install the rules of this schedule on the server, replacing any previously installed
+    /// entries of the same name.
+    pub fn install(&self) -> Result<()> {
+        self.uninstall()?;
+        for (i, rule) in self.rules.iter().enumerate() {
+            for (command, name) in rule.commands().iter().zip(&self.entry_names(i)) {
+                self.server.schedule(name, &rule.time(), "24:00:00", command)?;
+            }
+            // Record each rule as it is installed, so that a partial install can be undone.
+            self.set_installed_rules(i as i64 + 1)?;
+        }
+        Ok(())
+    }
+
+    /// Get the names of the `schedule2` entries installed on the server by [`install`].
+    ///
+    /// [`install`]: crate::ThrottleSchedule::install
+    pub fn installed(&self) -> Result<Vec<String>> {
+        let rules = self.installed_rules()?.max(0) as usize;
+        Ok((0..rules).flat_map(|i| self.entry_names(i).to_vec()).collect())
+    }
+
+    /// Remove the `schedule2` entries installed on the server by [`install`].  Limits currently
+    /// in effect are not changed.
+    ///
+    /// [`install`]: crate::ThrottleSchedule::install
+    pub fn uninstall(&self) -> Result<()> {
+        let installed = self.installed()?;
+        if installed.is_empty() {
+            return Ok(());
+        }
+        for name in &installed {
+            self.server.schedule_remove(name)?;
+        }
+        self.set_installed_rules(0)
+    }
+
+    // The names of the download and upload entries of the `i`th rule.
+    fn entry_names(&self, i: usize) -> [String; 2] {
+        [format!("{}_{}_down", self.name, i), format!("{}_{}_up", self.name, i)]
+    }
+
+    // The name of the variable recording the number of installed rules.
+    fn record_name(&self) -> String {
+        format!("throttle_schedule.{}", self.name)
+    }
+
+    fn installed_rules(&self) -> Result<i64> {
+        match self.server.call::<i64>(&self.record_name(), &[&""]) {
+            // Nothing was ever installed under this name.
+            Err(e) if e.kind() == ErrorKind::Unsupported => Ok(0),
+            res => res,
+        }
+    }
+
+    fn set_installed_rules(&self, count: i64) -> Result<()> {
+        let record = self.record_name();
+        match self.server.call::<()>(&format!("{}.set", record), &[&"", &count]) {
+            Err(e) if e.kind() == ErrorKind::Unsupported => {
+                self.server.call("method.insert.value", &[&"", &record, &count])
+            }
+            res => res,
+        }
+    }
+}
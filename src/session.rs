@@ -0,0 +1,234 @@
+/*! Session backup and restore
+
+This module defines the [`SessionBackup`] and [`DownloadBackup`] types and support code.
+
+[`DownloadBackup`]: crate::DownloadBackup
+[`SessionBackup`]: crate::SessionBackup
+!*/
+
+use crate::multicall::{d, system::SystemMultiCall};
+use crate::{call_method, Download, Priority, Result, Server, TryFromValue};
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+use xmlrpc::Value;
+
+// How long to wait for a restored metafile to finish loading.
+const LOAD_POLL_INTERVAL: Duration = Duration::from_millis(100);
+const LOAD_TIMEOUT: Duration = Duration::from_secs(30);
+
+// The getters `DownloadBackup::from_row` expects, in order, optionally followed by
+// "d.custom.keys".
+const COLUMNS: &[&str] = &["d.hash", "d.name", "d.directory_base", "d.custom1", "d.custom2",
+                           "d.custom3", "d.custom4", "d.custom5", "d.priority", "d.views",
+                           "d.state"];
+
+/// The state of a single download, as captured by [`DownloadBackup::capture`]
+///
+/// [`DownloadBackup::capture`]: crate::DownloadBackup::capture
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug)]
+pub struct DownloadBackup {
+    /// The infohash of the download.
+    pub hash: String,
+    /// The name of the download.
+    pub name: String,
+    /// The contents of the download's metafile (see [`Download::metafile`]).
+    ///
+    /// [`Download::metafile`]: crate::Download::metafile
+    pub metafile: Vec<u8>,
+    /// The path of the download's data (`d.directory_base`).
+    pub directory: String,
+    /// The `custom1` through `custom5` user-defined fields (`custom1` holds the label).
+    pub custom: [String; 5],
+    /// The keyed user-defined custom fields (see [`Download::custom_keys`]).  Empty if the server
+    /// does not support `d.custom.keys`.
+    ///
+    /// [`Download::custom_keys`]: crate::Download::custom_keys
+    pub custom_fields: BTreeMap<String, String>,
+    /// The priority of the download.
+    pub priority: Priority,
+    /// The views the download is tagged into (see [`Download::views`]).
+    ///
+    /// [`Download::views`]: crate::Download::views
+    pub views: Vec<String>,
+    /// Whether the download was started.
+    pub started: bool,
+}
+
+impl DownloadBackup {
+    /// Capture the state of `download`.
+    pub fn capture(download: &Download) -> Result<Self> {
+        let server = download.server();
+        let mut backups = capture(server, d::MultiBuilder::for_hashes(server,
+                                                                       &[download.sha1_hex()]))?;
+        Ok(backups.remove(0))
+    }
+
+    /// Load this download into `server` (if it is not already loaded), and apply the captured
+    /// state to it.
+    ///
+    /// The data directory is only set on newly loaded downloads; rtorrent does not allow changing
+    /// the directory of an open download.
+    pub fn restore(&self, server: &Server) -> Result<Download> {
+        let mut download = Download::from_hash(server, &self.hash);
+        let loaded = !download.exists()?;
+        if loaded {
+            download = server.load_torrent_bytes(&self.metafile, false)?;
+            download = server.await_load(download, LOAD_POLL_INTERVAL, LOAD_TIMEOUT)?;
+        }
+
+        let target = Value::from(&download);
+        let mut mc = SystemMultiCall::new();
+        if loaded {
+            mc.push("d.directory_base.set",
+                    vec![target.clone(), Value::from(self.directory.as_str())]);
+        }
+        for (i, custom) in self.custom.iter().enumerate() {
+            mc.push(&format!("d.custom{}.set", i + 1),
+                    vec![target.clone(), Value::from(custom.as_str())]);
+        }
+        for (key, value) in &self.custom_fields {
+            mc.push("d.custom.set",
+                    vec![target.clone(), Value::from(key.as_str()), Value::from(value.as_str())]);
+        }
+        mc.push("d.priority.set", vec![target.clone(), Value::from(self.priority)]);
+        for view in &self.views {
+            mc.push("d.views.push_back_unique", vec![target.clone(), Value::from(view.as_str())]);
+            mc.push("view.set_visible", vec![target.clone(), Value::from(view.as_str())]);
+        }
+        if self.started {
+            mc.push("d.start", vec![target]);
+        }
+        mc.invoke(server)?;
+        Ok(download)
+    }
+
+    fn from_row(row: &[Value]) -> Result<Self> {
+        Ok(Self {
+            hash: String::try_from_value(&row[0])?,
+            name: String::try_from_value(&row[1])?,
+            metafile: Vec::new(),
+            directory: String::try_from_value(&row[2])?,
+            custom: [
+                String::try_from_value(&row[3])?,
+                String::try_from_value(&row[4])?,
+                String::try_from_value(&row[5])?,
+                String::try_from_value(&row[6])?,
+                String::try_from_value(&row[7])?,
+            ],
+            custom_fields: BTreeMap::new(),
+            priority: Priority::try_from_value(&row[8])?,
+            views: Vec::<String>::try_from_value(&row[9])?,
+            started: bool::try_from_value(&row[10])?,
+        })
+    }
+}
+
+/// The state of an entire rtorrent instance, as captured by [`SessionBackup::capture`]
+///
+/// A backup can be restored into the same instance or another one with
+/// [`SessionBackup::restore`].  With the `serde` feature, it can be serialized to a file in any
+/// serde format.
+///
+/// ```no_run
+/// use rtorrent_xmlrpc_bindings as rtorrent;
+///
+/// let old = rtorrent::Server::new("http://1.2.3.4/RPC2");
+/// let new = rtorrent::Server::new("http://5.6.7.8/RPC2");
+///
+/// let backup = rtorrent::SessionBackup::capture(&old)?;
+/// let restored = backup.restore(&new)?;
+/// println!("Restored {} of {} downloads", restored.len(), backup.downloads.len());
+/// # Ok::<(), rtorrent::Error>(())
+/// ```
+///
+/// [`SessionBackup::capture`]: crate::SessionBackup::capture
+/// [`SessionBackup::restore`]: crate::SessionBackup::restore
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug)]
+pub struct SessionBackup {
+    /// The names of the instance's views (`view.list`).  View filters and sort orders are not
+    /// captured.
+    pub views: Vec<String>,
+    /// The state of each download.
+    pub downloads: Vec<DownloadBackup>,
+}
+
+impl SessionBackup {
+    /// Capture the state of every download on `server`.
+    ///
+    /// Download state is gathered with a couple of multicalls, but each metafile is retrieved
+    /// separately (see [`Download::metafile`]).
+    ///
+    /// [`Download::metafile`]: crate::Download::metafile
+    pub fn capture(server: &Server) -> Result<Self> {
+        Ok(Self {
+            views: view_list(server)?,
+            downloads: capture(server, d::MultiBuilder::new(server, "main"))?,
+        })
+    }
+
+    /// Restore this backup into `server`: create any missing views, then restore each download
+    /// (see [`DownloadBackup::restore`]).
+    ///
+    /// [`DownloadBackup::restore`]: crate::DownloadBackup::restore
+    pub fn restore(&self, server: &Server) -> Result<Vec<Download>> {
+        let existing = view_list(server)?.into_iter().collect::<BTreeSet<_>>();
+        for view in self.views.iter().filter(|view| !existing.contains(*view)) {
+            call_method::<()>(server.endpoint(), "view.add",
+                              vec![Value::from(""), Value::from(view.as_str())])?;
+        }
+        self.downloads.iter()
+            .map(|backup| backup.restore(server))
+            .collect()
+    }
+}
+
+fn view_list(server: &Server) -> Result<Vec<String>> {
+    call_method(server.endpoint(), "view.list", vec![Value::from("")])
+}
+
+// Capture the state of the downloads `builder` queries.
+fn capture(server: &Server, builder: d::MultiBuilder) -> Result<Vec<DownloadBackup>> {
+    let with_keys = server.supported_methods()?.contains("d.custom.keys");
+    let mut builder = builder.dynamic();
+    for column in COLUMNS {
+        builder.push(column);
+    }
+    if with_keys {
+        builder.push("d.custom.keys");
+    }
+    let mut backups = builder.invoke()?
+        .iter()
+        .map(|row| {
+            let keys = if with_keys {
+                Vec::<String>::try_from_value(&row[COLUMNS.len()])?
+            } else {
+                Vec::new()
+            };
+            Ok((DownloadBackup::from_row(row)?, keys))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // Fetch the keyed custom fields of every download in a single round trip.
+    let mut mc = SystemMultiCall::new();
+    for (backup, keys) in &backups {
+        for key in keys {
+            mc.push("d.custom", vec![Value::from(backup.hash.as_str()), Value::from(key.as_str())]);
+        }
+    }
+    let mut values = if mc.is_empty() {
+        Vec::new()
+    } else {
+        mc.invoke(server)?
+    }.into_iter();
+
+    for (backup, keys) in &mut backups {
+        for key in keys.drain(..) {
+            let value = values.next().unwrap_or_else(|| Value::from(""));
+            backup.custom_fields.insert(key, String::try_from_value(&value)?);
+        }
+        backup.metafile = Download::from_hash(server, &backup.hash).metafile()?;
+    }
+    Ok(backups.into_iter().map(|(backup, _)| backup).collect())
+}
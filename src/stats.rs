@@ -0,0 +1,88 @@
+/*! Global statistics
+
+This module defines the [`Stats`] type and support code.
+
+[`Stats`]: crate::Stats
+!*/
+
+use crate::multicall::system::SystemMultiCall;
+use crate::{Result, Server, TryFromValue};
+use xmlrpc::Value;
+
+/// A snapshot of global statistics for an rtorrent instance
+///
+/// Obtained from [`Server::stats`].
+///
+/// ```no_run
+/// use rtorrent_xmlrpc_bindings as rtorrent;
+///
+/// let my_handle = rtorrent::Server::new("http://1.2.3.4/RPC2");
+/// let stats = my_handle.stats()?;
+/// println!("{} downloads, down {} B/s, up {} B/s",
+///     stats.downloads, stats.down_rate, stats.up_rate);
+/// # Ok::<(), rtorrent::Error>(())
+/// ```
+///
+/// [`Server::stats`]: crate::Server::stats
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug)]
+pub struct Stats {
+    /// The current download rate for this instance (bytes/s).
+    pub down_rate: i64,
+    /// The current upload rate for this instance (bytes/s).
+    pub up_rate: i64,
+    /// The total downloaded metric for this instance (bytes).
+    pub down_total: i64,
+    /// The total uploaded metric for this instance (bytes).
+    pub up_total: i64,
+    /// The configured global download rate limit (bytes/s).  Zero is unlimited.
+    pub down_max_rate: i64,
+    /// The configured global upload rate limit (bytes/s).  Zero is unlimited.
+    pub up_max_rate: i64,
+    /// The number of downloads loaded in this instance.
+    pub downloads: i64,
+    /// The number of sockets currently open.
+    pub open_sockets: i64,
+    /// The number of files currently open.
+    pub open_files: i64,
+    /// Seconds elapsed since this instance was started.
+    pub uptime: i64,
+}
+
+impl Stats {
+    pub(crate) fn fetch(server: &Server) -> Result<Self> {
+        let mut mc = SystemMultiCall::new();
+        for method in &[
+            "throttle.global_down.rate",
+            "throttle.global_up.rate",
+            "throttle.global_down.total",
+            "throttle.global_up.total",
+            "throttle.global_down.max_rate",
+            "throttle.global_up.max_rate",
+            "network.open_sockets",
+            "network.open_files",
+            "system.startup_time",
+            "system.time_seconds",
+        ] {
+            mc.push(method, vec![]);
+        }
+        mc.push("view.size", vec![Value::from(""), Value::from("main")]);
+
+        let vals = mc.invoke(server)?
+            .iter()
+            .map(i64::try_from_value)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            down_rate: vals[0],
+            up_rate: vals[1],
+            down_total: vals[2],
+            up_total: vals[3],
+            down_max_rate: vals[4],
+            up_max_rate: vals[5],
+            open_sockets: vals[6],
+            open_files: vals[7],
+            uptime: vals[9] - vals[8],
+            downloads: vals[10],
+        })
+    }
+}
@@ -0,0 +1,119 @@
+/*! Timestamps
+
+This module defines the [`Timestamp`] type and support code.
+
+[`Timestamp`]: crate::Timestamp
+!*/
+
+use crate::value_conversion::{ToValue, TryFromValue};
+use crate::Result;
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use xmlrpc::Value;
+
+/// A point in time reported by rtorrent, in seconds since Unix epoch
+///
+/// rtorrent reports many times (e.g., `d.creation_date`, `d.load_date`, `d.timestamp.finished`,
+/// and tracker activity times) as integer seconds since Unix epoch, using zero for "never."
+/// `Timestamp` can be used as a result type wherever such a value is returned: convert the `i64`
+/// from a getter with `Timestamp::from`, or use an `i64` multicall operation's `as_timestamp()`
+/// variant as a column.
+///
+/// ```no_run
+/// use rtorrent_xmlrpc_bindings as rtorrent;
+/// use rtorrent::{multicall::d, Timestamp};
+///
+/// let my_handle = rtorrent::Server::new("http://1.2.3.4/RPC2");
+///
+/// for (name, finished) in d::MultiBuilder::new(&my_handle, "default")
+///     .call(d::NAME)
+///     .call(d::TIMESTAMP_FINISHED.as_timestamp())
+///     .invoke()? {
+///     match finished.to_system_time() {
+///         Some(time) => println!("{}: finished at {:?}", name, time),
+///         None => println!("{}: not finished", name),
+///     }
+/// }
+/// # Ok::<(), rtorrent::Error>(())
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(pub i64);
+
+impl Timestamp {
+    /// Get the number of seconds since Unix epoch.
+    pub fn as_secs(self) -> i64 {
+        self.0
+    }
+
+    /// Is this the zero timestamp rtorrent uses for "never"?
+    pub fn is_unset(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Convert to a `SystemTime`, or `None` if the timestamp is unset (zero).
+    pub fn to_system_time(self) -> Option<SystemTime> {
+        if self.is_unset() {
+            return None;
+        }
+        let offset = Duration::from_secs(self.0.unsigned_abs());
+        if self.0 > 0 {
+            UNIX_EPOCH.checked_add(offset)
+        } else {
+            UNIX_EPOCH.checked_sub(offset)
+        }
+    }
+
+    /// Get the time elapsed between this timestamp and now, or `None` if the timestamp is unset
+    /// or in the future.
+    pub fn elapsed(self) -> Option<Duration> {
+        self.to_system_time()?.elapsed().ok()
+    }
+}
+
+impl From<i64> for Timestamp {
+    fn from(secs: i64) -> Self {
+        Self(secs)
+    }
+}
+
+impl From<Timestamp> for i64 {
+    fn from(ts: Timestamp) -> Self {
+        ts.0
+    }
+}
+
+/// Convert a `SystemTime` to whole seconds since Unix epoch (truncating toward the epoch).
+impl From<SystemTime> for Timestamp {
+    fn from(time: SystemTime) -> Self {
+        match time.duration_since(UNIX_EPOCH) {
+            Ok(d) => Self(d.as_secs() as i64),
+            Err(e) => Self(-(e.duration().as_secs() as i64)),
+        }
+    }
+}
+
+impl TryFromValue for Timestamp {
+    fn try_from_value(val: &Value) -> Result<Self> {
+        i64::try_from_value(val).map(Self)
+    }
+}
+
+impl From<Timestamp> for Value {
+    fn from(ts: Timestamp) -> Self {
+        Value::Int64(ts.0)
+    }
+}
+
+impl ToValue for Timestamp {
+    fn to_value(&self) -> Value {
+        Value::from(*self)
+    }
+}
+
+/// Displays the timestamp as seconds since Unix epoch.
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -1,8 +1,24 @@
 use crate::{Error, Result, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
 
-/// Essentially TryFrom<Value> with crate::Error, but we need our own trait because crates are not
-/// allowed to define implementations of traits from foreign crates on types from forein crates.
+// Essentially TryFrom<Value> with crate::Error, but we need our own trait because crates are not
+// allowed to define implementations of traits from foreign crates on types from forein crates.
+
+/// Conversion from an XMLRPC [`Value`] returned by rtorrent into a Rust type
+///
+/// This is implemented for rtorrent's primitive types, lists, and structs, and for the typed
+/// results in this crate.  Implement it (or use [`try_from_value_struct!`]) for your own types to
+/// use them as multicall column types or results.
+///
+/// [`Value`]: crate::Value
+/// [`try_from_value_struct!`]: crate::try_from_value_struct
 pub trait TryFromValue: Sized {
+    /// Convert `val`, or fail with [`Error::UnexpectedStructure`].
+    ///
+    /// [`Error::UnexpectedStructure`]: crate::Error::UnexpectedStructure
     fn try_from_value(val: &Value) -> Result<Self>;
 }
 
@@ -19,6 +35,23 @@ impl TryFromValue for i64 {
     }
 }
 
+// Narrower and unsigned integers, range-checked.
+macro_rules! int_try_from_value {
+    ($($ty: ty),*) => {
+        $(
+            impl TryFromValue for $ty {
+                fn try_from_value(val: &Value) -> Result<Self> {
+                    let i = i64::try_from_value(val)?;
+                    <$ty>::try_from(i).map_err(|_| Error::UnexpectedStructure(
+                        format!("Got {}, out of range for {}", i, stringify!($ty))
+                    ))
+                }
+            }
+        )*
+    }
+}
+int_try_from_value!(i32, u32, u64, usize);
+
 // rtorrent expresses a few statistics as fixed-point decimal; treat as floats.
 impl TryFromValue for f64 {
     fn try_from_value(val: &Value) -> Result<Self> {
@@ -56,6 +89,28 @@ impl TryFromValue for String {
     }
 }
 
+// Addresses are strings; IPv6 addresses may be enclosed in brackets.
+impl TryFromValue for IpAddr {
+    fn try_from_value(val: &Value) -> Result<Self> {
+        let s = string(val)?;
+        s.trim_start_matches('[').trim_end_matches(']')
+            .parse()
+            .map_err(|_| Error::UnexpectedStructure(
+                format!("Got {:?}, expected an IP address", val)
+            ))
+    }
+}
+
+impl TryFromValue for SocketAddr {
+    fn try_from_value(val: &Value) -> Result<Self> {
+        string(val)?
+            .parse()
+            .map_err(|_| Error::UnexpectedStructure(
+                format!("Got {:?}, expected a socket address", val)
+            ))
+    }
+}
+
 // Void is represented as zero-valued int, but we'll accept nil.
 impl TryFromValue for () {
     fn try_from_value(val: &Value) -> Result<Self> {
@@ -77,3 +132,224 @@ pub(crate) fn list(val: &Value) -> Result<&Vec<Value>> {
         )),
     }
 }
+
+// Like `list`, but takes ownership of the elements rather than borrowing them.
+pub(crate) fn into_list(val: Value) -> Result<Vec<Value>> {
+    match val {
+        Value::Array(a) => Ok(a),
+        _ => Err(Error::UnexpectedStructure(
+            format!("Got {:?}, expected array", val)
+        )),
+    }
+}
+
+impl<T: TryFromValue> TryFromValue for Vec<T> {
+    fn try_from_value(val: &Value) -> Result<Self> {
+        list(val)?
+            .iter()
+            .map(T::try_from_value)
+            .collect()
+    }
+}
+
+pub(crate) fn structure(val: &Value) -> Result<&BTreeMap<String, Value>> {
+    match val {
+        Value::Struct(s) => Ok(s),
+        _ => Err(Error::UnexpectedStructure(
+            format!("Got {:?}, expected struct", val)
+        )),
+    }
+}
+
+impl<T: TryFromValue> TryFromValue for BTreeMap<String, T> {
+    fn try_from_value(val: &Value) -> Result<Self> {
+        structure(val)?
+            .iter()
+            .map(|(k, v)| Ok((k.clone(), T::try_from_value(v)?)))
+            .collect()
+    }
+}
+
+impl<T: TryFromValue> TryFromValue for HashMap<String, T> {
+    fn try_from_value(val: &Value) -> Result<Self> {
+        structure(val)?
+            .iter()
+            .map(|(k, v)| Ok((k.clone(), T::try_from_value(v)?)))
+            .collect()
+    }
+}
+
+// The raw value, for results (or struct members) without a more specific type.
+impl TryFromValue for Value {
+    fn try_from_value(val: &Value) -> Result<Self> {
+        Ok(val.clone())
+    }
+}
+
+/// Conversion from a Rust type into an XMLRPC [`Value`] argument, using rtorrent's encoding
+///
+/// This mirrors [`TryFromValue`].  For example, booleans are passed as integers, paths as
+/// strings, byte slices as base64, and [`Download`]s (and other items) as their rtorrent target
+/// identifier.  It is used for the arguments of [`Server::call`].
+///
+/// [`Value`]: crate::Value
+/// [`TryFromValue`]: crate::TryFromValue
+/// [`Download`]: crate::Download
+/// [`Server::call`]: crate::Server::call
+pub trait ToValue {
+    /// Convert `self` into an XMLRPC value.
+    fn to_value(&self) -> Value;
+}
+
+impl<T: ToValue + ?Sized> ToValue for &T {
+    fn to_value(&self) -> Value {
+        (**self).to_value()
+    }
+}
+
+impl ToValue for Value {
+    fn to_value(&self) -> Value {
+        self.clone()
+    }
+}
+
+// rtorrent has no boolean arguments; flags are integers.
+impl ToValue for bool {
+    fn to_value(&self) -> Value {
+        Value::Int64(*self as i64)
+    }
+}
+
+macro_rules! int_to_value {
+    ($($ty: ty),*) => {
+        $(
+            impl ToValue for $ty {
+                fn to_value(&self) -> Value {
+                    Value::Int64(*self as i64)
+                }
+            }
+        )*
+    }
+}
+int_to_value!(i64, i32, u32, u16, u8);
+
+impl ToValue for str {
+    fn to_value(&self) -> Value {
+        Value::from(self)
+    }
+}
+
+impl ToValue for String {
+    fn to_value(&self) -> Value {
+        Value::from(self.as_str())
+    }
+}
+
+impl ToValue for Path {
+    fn to_value(&self) -> Value {
+        Value::from(self.to_string_lossy().as_ref())
+    }
+}
+
+impl ToValue for PathBuf {
+    fn to_value(&self) -> Value {
+        self.as_path().to_value()
+    }
+}
+
+impl ToValue for IpAddr {
+    fn to_value(&self) -> Value {
+        Value::from(self.to_string())
+    }
+}
+
+impl ToValue for SocketAddr {
+    fn to_value(&self) -> Value {
+        Value::from(self.to_string())
+    }
+}
+
+impl ToValue for [u8] {
+    fn to_value(&self) -> Value {
+        Value::Base64(self.to_vec())
+    }
+}
+
+impl ToValue for Vec<u8> {
+    fn to_value(&self) -> Value {
+        Value::Base64(self.clone())
+    }
+}
+
+// Member `name` of an XMLRPC struct, or element `index` of an array, for
+// `try_from_value_struct!`.
+#[doc(hidden)]
+pub fn struct_field<T: TryFromValue>(val: &Value, index: usize, name: &str) -> Result<T> {
+    let field = match val {
+        Value::Struct(s) => s.get(name),
+        Value::Array(a) => a.get(index),
+        _ => return Err(Error::UnexpectedStructure(
+            format!("Got {:?}, expected struct or array", val)
+        )),
+    };
+    match field {
+        Some(field) => T::try_from_value(field),
+        None => Err(Error::UnexpectedStructure(
+            format!("Got {:?}, missing field {:?} (index {})", val, name, index)
+        )),
+    }
+}
+
+/// Define a struct and implement [`TryFromValue`] for it
+///
+/// The struct is converted from an XMLRPC struct by field name, or from an XMLRPC array (such as
+/// a multicall row) by field position.  Each field's type must itself implement
+/// [`TryFromValue`].
+///
+/// ```no_run
+/// use rtorrent_xmlrpc_bindings as rtorrent;
+/// use rtorrent::TryFromValue;
+///
+/// rtorrent::try_from_value_struct! {
+///     /// Selected DHT statistics.
+///     #[derive(Debug)]
+///     pub struct DhtCounts {
+///         pub nodes: i64,
+///         pub peers: i64,
+///     }
+/// }
+///
+/// let raw = xmlrpc::Request::new("dht.statistics").call_url("http://1.2.3.4/RPC2")?;
+/// println!("{:?}", DhtCounts::try_from_value(&raw)?);
+/// # Ok::<(), rtorrent::Error>(())
+/// ```
+///
+/// [`TryFromValue`]: crate::TryFromValue
+#[macro_export]
+macro_rules! try_from_value_struct {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $( $(#[$fmeta:meta])* $fvis:vis $field:ident : $ty:ty ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $( $(#[$fmeta])* $fvis $field: $ty, )*
+        }
+
+        impl $crate::TryFromValue for $name {
+            #[allow(unused_assignments, unused_mut, unused_variables)]
+            fn try_from_value(val: &$crate::Value) -> $crate::Result<Self> {
+                let mut index = 0usize;
+                Ok(Self {
+                    $( $field: {
+                        let field = $crate::__struct_field::<$ty>(val, index, stringify!($field))?;
+                        index += 1;
+                        field
+                    }, )*
+                })
+            }
+        }
+    };
+}
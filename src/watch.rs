@@ -0,0 +1,330 @@
+/*! Polling for changes to downloads
+
+This module defines the [`Watcher`] type, which periodically queries the downloads in a view and
+reports changes between successive snapshots as [`Event`]s.
+
+rtorrent's XMLRPC interface has no push notifications, so a `Watcher` polls: each poll is a single
+`d.multicall2` over the view.  The first poll establishes a baseline and reports no events.
+
+```no_run
+use rtorrent_xmlrpc_bindings as rtorrent;
+use rtorrent::watch::{Event, Watcher};
+use std::time::Duration;
+
+let my_handle = rtorrent::Server::new("http://1.2.3.4/RPC2");
+
+let events = Watcher::new(&my_handle, "main").spawn(Duration::from_secs(10));
+for event in events {
+    match event? {
+        Event::DownloadFinished(dl) => println!("Finished: {}", dl.name()?),
+        other => println!("{:?}", other),
+    }
+}
+# Ok::<(), rtorrent::Error>(())
+```
+
+[`Event`]: crate::watch::Event
+[`Watcher`]: crate::watch::Watcher
+!*/
+
+use crate::download::STATUS_METHODS;
+use crate::multicall::d;
+use crate::{Download, DownloadStatus, Result, Server, TryFromValue};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+/// A change observed between two polls of a [`Watcher`]
+///
+/// [`Watcher`]: crate::watch::Watcher
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// A download appeared in the view.
+    DownloadAdded(Download),
+    /// A download finished downloading.
+    DownloadFinished(Download),
+    /// A download disappeared from the view (it was erased, or no longer matches the view).
+    DownloadRemoved(Download),
+    /// The overall state of a download changed.
+    StateChanged {
+        download: Download,
+        old: DownloadStatus,
+        new: DownloadStatus,
+    },
+    /// A download's tracker reported a new error, with the given message.
+    TrackerError {
+        download: Download,
+        message: String,
+    },
+}
+
+// What a `Watcher` remembers about each download between polls.
+struct Snapshot {
+    status: DownloadStatus,
+    message: String,
+    complete: bool,
+}
+
+/// `Watcher` polls the downloads in a view and reports changes as [`Event`]s
+///
+/// Events can be collected one poll at a time with [`poll`], delivered to a callback with
+/// [`run`], or delivered over a channel from a background thread with [`spawn`].
+///
+/// [`Event`]: crate::watch::Event
+/// [`poll`]: crate::watch::Watcher::poll
+/// [`run`]: crate::watch::Watcher::run
+/// [`spawn`]: crate::watch::Watcher::spawn
+pub struct Watcher {
+    server: Server,
+    view: String,
+    // Keyed by infohash; `None` until the first poll.
+    last: Option<BTreeMap<String, Snapshot>>,
+}
+
+impl Watcher {
+    /// Create a `Watcher` over the downloads in `view` (e.g., `"main"`).
+    pub fn new(server: &Server, view: &str) -> Self {
+        Self {
+            server: server.clone(),
+            view: view.to_owned(),
+            last: None,
+        }
+    }
+
+    /// Query the view once, and return the events since the previous poll.
+    ///
+    /// The first poll only records a baseline, and returns no events.  If a poll fails, the
+    /// baseline is kept, and the next successful poll reports all changes since the last
+    /// successful one.
+    pub fn poll(&mut self) -> Result<Vec<Event>> {
+        let current = self.snapshot()?;
+        Ok(match self.last.replace(current) {
+            Some(last) => diff(&self.server, &last, self.last.as_ref().unwrap()),
+            None => Vec::new(),
+        })
+    }
+
+    /// Poll every `interval`, passing each event to `callback`, for as long as `callback` returns
+    /// `true`.
+    ///
+    /// Returns the first error encountered while polling.
+    pub fn run<F>(&mut self, interval: Duration, mut callback: F) -> Result<()>
+        where F: FnMut(Event) -> bool {
+        loop {
+            for event in self.poll()? {
+                if !callback(event) {
+                    return Ok(());
+                }
+            }
+            thread::sleep(interval);
+        }
+    }
+
+    /// Poll every `interval` on a background thread, and return the resulting events.
+    ///
+    /// Errors are delivered, too; polling continues after an error (see [`poll`]).  The background
+    /// thread stops polling once the returned [`Events`] is dropped (within one `interval`).
+    ///
+    /// [`poll`]: crate::watch::Watcher::poll
+    /// [`Events`]: crate::watch::Events
+    pub fn spawn(mut self, interval: Duration) -> Events {
+        let (tx, rx) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = stop.clone();
+        thread::spawn(move || while !stopped.load(Ordering::Relaxed) {
+            let sent = match self.poll() {
+                Ok(events) => events.into_iter().all(|event| tx.send(Ok(event)).is_ok()),
+                Err(e) => tx.send(Err(e)).is_ok(),
+            };
+            if !sent {
+                return;
+            }
+            thread::sleep(interval);
+        });
+        Events { receiver: rx, stop }
+    }
+
+    fn snapshot(&self) -> Result<BTreeMap<String, Snapshot>> {
+        let mut builder = d::MultiBuilder::new(&self.server, &self.view)
+            .dynamic()
+            .call("d.hash");
+        for method in STATUS_METHODS {
+            builder.push(method);
+        }
+        builder.invoke()?
+            .into_iter()
+            .map(|row| {
+                let hash = String::try_from_value(&row[0])?;
+                // The status columns are "d.hashing", "d.message", ..., "d.complete".
+                let vals = &row[1..];
+                Ok((hash, Snapshot {
+                    status: DownloadStatus::from_values(vals)?,
+                    message: String::try_from_value(&vals[1])?,
+                    complete: bool::try_from_value(&vals[5])?,
+                }))
+            })
+            .collect()
+    }
+}
+
+/// The events of a [`Watcher`] polling on a background thread, as returned by [`Watcher::spawn`]
+///
+/// Iterating blocks until the next event.  Dropping `Events` stops the background thread.
+///
+/// [`Watcher`]: crate::watch::Watcher
+/// [`Watcher::spawn`]: crate::watch::Watcher::spawn
+#[derive(Debug)]
+pub struct Events {
+    receiver: mpsc::Receiver<Result<Event>>,
+    stop: Arc<AtomicBool>,
+}
+
+impl Events {
+    /// Get the channel the events are delivered on, e.g., to wait for an event with a timeout.
+    pub fn receiver(&self) -> &mpsc::Receiver<Result<Event>> {
+        &self.receiver
+    }
+}
+
+impl Iterator for Events {
+    type Item = Result<Event>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.receiver.recv().ok()
+    }
+}
+
+impl Drop for Events {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+// The events between the snapshots `last` and `current` of downloads on `server`.
+fn diff(server: &Server, last: &BTreeMap<String, Snapshot>, current: &BTreeMap<String, Snapshot>)
+    -> Vec<Event> {
+    let mut events = Vec::new();
+    for (hash, snap) in current {
+        let download = Download::from_hash(server, hash);
+        let old = match last.get(hash) {
+            Some(old) => old,
+            None => {
+                events.push(Event::DownloadAdded(download));
+                continue;
+            }
+        };
+        if snap.status != old.status {
+            events.push(Event::StateChanged {
+                download: download.clone(),
+                old: old.status.clone(),
+                new: snap.status.clone(),
+            });
+        }
+        if snap.complete && !old.complete {
+            events.push(Event::DownloadFinished(download.clone()));
+        }
+        if snap.message != old.message && is_tracker_message(&snap.message) {
+            events.push(Event::TrackerError { download, message: snap.message.clone() });
+        }
+    }
+    for hash in last.keys().filter(|hash| !current.contains_key(*hash)) {
+        events.push(Event::DownloadRemoved(Download::from_hash(server, hash)));
+    }
+    events
+}
+
+// rtorrent prefixes messages relayed from the tracker (e.g., failure reasons) with "Tracker:".
+fn is_tracker_message(message: &str) -> bool {
+    message.starts_with("Tracker:")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const A: &str = "0123456789ABCDEF0123456789ABCDEF01234567";
+    const B: &str = "89ABCDEF0123456789ABCDEF0123456789ABCDEF";
+
+    fn snap(status: DownloadStatus, message: &str, complete: bool) -> Snapshot {
+        Snapshot { status, message: message.to_owned(), complete }
+    }
+
+    fn snapshots(snaps: Vec<(&str, Snapshot)>) -> BTreeMap<String, Snapshot> {
+        snaps.into_iter().map(|(hash, snap)| (hash.to_owned(), snap)).collect()
+    }
+
+    #[test]
+    fn unchanged() {
+        let server = Server::new("http://localhost/RPC2");
+        let last = snapshots(vec![(A, snap(DownloadStatus::Seeding, "", true))]);
+        let current = snapshots(vec![(A, snap(DownloadStatus::Seeding, "", true))]);
+        assert!(diff(&server, &last, &current).is_empty());
+    }
+
+    #[test]
+    fn added_and_removed() {
+        let server = Server::new("http://localhost/RPC2");
+        let last = snapshots(vec![(A, snap(DownloadStatus::Stopped, "", false))]);
+        let current = snapshots(vec![(B, snap(DownloadStatus::Downloading, "", false))]);
+        assert_eq!(diff(&server, &last, &current), vec![
+            Event::DownloadAdded(Download::from_hash(&server, B)),
+            Event::DownloadRemoved(Download::from_hash(&server, A)),
+        ]);
+    }
+
+    #[test]
+    fn finished() {
+        let server = Server::new("http://localhost/RPC2");
+        let download = Download::from_hash(&server, A);
+        let last = snapshots(vec![(A, snap(DownloadStatus::Downloading, "", false))]);
+        let current = snapshots(vec![(A, snap(DownloadStatus::Seeding, "", true))]);
+        assert_eq!(diff(&server, &last, &current), vec![
+            Event::StateChanged {
+                download: download.clone(),
+                old: DownloadStatus::Downloading,
+                new: DownloadStatus::Seeding,
+            },
+            Event::DownloadFinished(download),
+        ]);
+        // Completion is only reported once.
+        assert!(diff(&server, &current, &current).iter()
+                .all(|event| !matches!(event, Event::DownloadFinished(_))));
+    }
+
+    #[test]
+    fn state_changed() {
+        let server = Server::new("http://localhost/RPC2");
+        let last = snapshots(vec![(A, snap(DownloadStatus::Seeding, "", true))]);
+        let current = snapshots(vec![(A, snap(DownloadStatus::Stopped, "", true))]);
+        assert_eq!(diff(&server, &last, &current), vec![Event::StateChanged {
+            download: Download::from_hash(&server, A),
+            old: DownloadStatus::Seeding,
+            new: DownloadStatus::Stopped,
+        }]);
+    }
+
+    #[test]
+    fn tracker_message() {
+        let server = Server::new("http://localhost/RPC2");
+        let download = Download::from_hash(&server, A);
+        let message = "Tracker: [Failure reason \"Unregistered torrent\"]";
+        let errored = DownloadStatus::Errored(message.to_owned());
+        let last = snapshots(vec![(A, snap(DownloadStatus::Seeding, "", true))]);
+        let current = snapshots(vec![(A, snap(errored.clone(), message, true))]);
+        assert_eq!(diff(&server, &last, &current), vec![
+            Event::StateChanged {
+                download: download.clone(),
+                old: DownloadStatus::Seeding,
+                new: errored.clone(),
+            },
+            Event::TrackerError { download, message: message.to_owned() },
+        ]);
+        // Repeated messages, and messages not from the tracker, are not reported.
+        assert!(diff(&server, &current, &current).is_empty());
+        let other = snapshots(vec![(A, snap(errored, "Storage error: disk full", true))]);
+        assert!(diff(&server, &current, &other).iter()
+                .all(|event| !matches!(event, Event::TrackerError { .. })));
+    }
+}
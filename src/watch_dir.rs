@@ -0,0 +1,241 @@
+/*! Loading `.torrent` files from a local directory
+
+This module defines the [`WatchDir`] type and support code.
+
+[`WatchDir`]: crate::WatchDir
+!*/
+
+use crate::download::label_encode;
+use crate::multicall::d;
+use crate::{bencode, call_method, quote_command_arg, value_conversion, Download, Error, Result,
+            Server};
+use std::collections::{BTreeMap, BTreeSet};
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+use xmlrpc::Value;
+
+/// The outcome of one `.torrent` file found by a [`WatchDir`] scan
+///
+/// [`WatchDir`]: crate::WatchDir
+#[derive(Debug)]
+pub enum WatchDirEvent {
+    /// The file was loaded into the server as `download`.
+    Loaded {
+        path: PathBuf,
+        download: Download,
+    },
+    /// The file was not loaded, because `download` was already loaded (from this or another
+    /// file, or by other means).
+    Duplicate {
+        path: PathBuf,
+        download: Download,
+    },
+    /// The file could not be read or parsed.  It is retried once it is modified (e.g., if it was
+    /// still being written).
+    Invalid {
+        path: PathBuf,
+        error: Error,
+    },
+}
+
+/// `WatchDir` loads `.torrent` files that appear in a local directory into a [`Server`]
+///
+/// This is the client-side counterpart of rtorrent's `load.start` watch schedules: the directory
+/// is on the local machine, and the files' contents are sent to the server (which need not share
+/// a filesystem with it).  The directory is polled; each [`scan`] lists it once, and loads the
+/// `.torrent` files that are new or modified since the previous scan.
+///
+/// Files are deduplicated by infohash: a file is not loaded if the server already has its
+/// download.  Loaded downloads can be given a label and a data directory, and are started or left
+/// stopped.
+///
+/// ```no_run
+/// use rtorrent_xmlrpc_bindings as rtorrent;
+/// use rtorrent::{WatchDir, WatchDirEvent};
+/// use std::time::Duration;
+///
+/// let my_handle = rtorrent::Server::new("http://1.2.3.4/RPC2");
+///
+/// WatchDir::new(&my_handle, "/home/me/Downloads")
+///     .label("manual")
+///     .directory("/data/manual")
+///     .start(true)
+///     .run(Duration::from_secs(5), |event| {
+///         if let WatchDirEvent::Loaded { path, download } = event {
+///             println!("Loaded {} from {}", download.sha1_hex(), path.display());
+///         }
+///         true
+///     })?;
+/// # Ok::<(), rtorrent::Error>(())
+/// ```
+///
+/// [`scan`]: crate::WatchDir::scan
+/// [`Server`]: crate::Server
+#[derive(Clone, Debug)]
+pub struct WatchDir {
+    server: Server,
+    path: PathBuf,
+    label: Option<String>,
+    directory: Option<String>,
+    start: bool,
+    delete_loaded: bool,
+    // The modification time of each file as of its last processing.
+    seen: BTreeMap<PathBuf, SystemTime>,
+}
+
+impl WatchDir {
+    /// Create a `WatchDir` loading the `.torrent` files in `path` into `server`, stopped and with
+    /// the server's default options.
+    pub fn new<P: AsRef<Path>>(server: &Server, path: P) -> Self {
+        Self {
+            server: server.clone(),
+            path: path.as_ref().to_owned(),
+            label: None,
+            directory: None,
+            start: false,
+            delete_loaded: false,
+            seen: BTreeMap::new(),
+        }
+    }
+
+    /// Give loaded downloads the ruTorrent-style label `label` (see [`Download::label`]).
+    ///
+    /// [`Download::label`]: crate::Download::label
+    pub fn label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_owned());
+        self
+    }
+
+    /// Store the data of loaded downloads in `directory` (a path on the rtorrent host), rather
+    /// than the server's default directory.
+    pub fn directory(mut self, directory: &str) -> Self {
+        self.directory = Some(directory.to_owned());
+        self
+    }
+
+    /// Start loaded downloads (if `start` is true), or leave them stopped.
+    pub fn start(mut self, start: bool) -> Self {
+        self.start = start;
+        self
+    }
+
+    /// Delete `.torrent` files (if `delete` is true) once they are loaded, or are duplicates.
+    /// Otherwise, files are left in place, and are only loaded again if they are modified.
+    pub fn delete_loaded(mut self, delete: bool) -> Self {
+        self.delete_loaded = delete;
+        self
+    }
+
+    /// List the directory once, and load the `.torrent` files that are new or modified since the
+    /// previous scan.
+    ///
+    /// Returns the outcome for each such file.  Errors listing the directory or talking to the
+    /// server are returned as `Err`, and the files not yet processed are retried on the next
+    /// scan.
+    pub fn scan(&mut self) -> Result<Vec<WatchDirEvent>> {
+        let mut candidates = Vec::new();
+        for entry in std::fs::read_dir(&self.path)? {
+            let path = entry?.path();
+            if path.extension() != Some(OsStr::new("torrent")) || !path.is_file() {
+                continue;
+            }
+            let modified = std::fs::metadata(&path)?.modified()?;
+            if self.seen.get(&path) != Some(&modified) {
+                candidates.push((path, modified));
+            }
+        }
+        self.seen.retain(|path, _| path.exists());
+
+        let mut events = Vec::new();
+        if candidates.is_empty() {
+            return Ok(events);
+        }
+        candidates.sort();
+        let mut loaded = server_hashes(&self.server)?;
+        for (path, modified) in candidates {
+            let (contents, hash) = match read_torrent(&path) {
+                Ok(torrent) => torrent,
+                Err(error) => {
+                    self.seen.insert(path.clone(), modified);
+                    events.push(WatchDirEvent::Invalid { path, error });
+                    continue;
+                }
+            };
+            let download = Download::from_hash(&self.server, &hash);
+            if loaded.contains(&hash) {
+                self.finish(&path, modified)?;
+                events.push(WatchDirEvent::Duplicate { path, download });
+                continue;
+            }
+            self.load(&contents)?;
+            loaded.insert(hash);
+            self.finish(&path, modified)?;
+            events.push(WatchDirEvent::Loaded { path, download });
+        }
+        Ok(events)
+    }
+
+    /// Scan every `interval`, passing each outcome to `callback`, for as long as `callback`
+    /// returns `true`.
+    ///
+    /// Returns the first error encountered while scanning.
+    pub fn run<F>(&mut self, interval: Duration, mut callback: F) -> Result<()>
+        where F: FnMut(WatchDirEvent) -> bool {
+        loop {
+            for event in self.scan()? {
+                if !callback(event) {
+                    return Ok(());
+                }
+            }
+            thread::sleep(interval);
+        }
+    }
+
+    // Load `contents`, applying the options in the same call so that the data directory is set
+    // before the download is started.
+    fn load(&self, contents: &[u8]) -> Result<()> {
+        let load = if self.start {
+            "load.raw_start_verbose"
+        } else {
+            "load.raw_verbose"
+        };
+        let mut args = vec![Value::from(""), Value::from(contents.to_vec())];
+        if let Some(directory) = &self.directory {
+            args.push(Value::from(format!("d.directory.set={}", quote_command_arg(directory))));
+        }
+        if let Some(label) = &self.label {
+            let label = quote_command_arg(&label_encode(label));
+            args.push(Value::from(format!("d.custom1.set={}", label)));
+        }
+        call_method(self.server.endpoint(), load, args)
+    }
+
+    // Record `path` as processed, deleting it if requested.
+    fn finish(&mut self, path: &Path, modified: SystemTime) -> Result<()> {
+        if self.delete_loaded {
+            std::fs::remove_file(path)?;
+        } else {
+            self.seen.insert(path.to_owned(), modified);
+        }
+        Ok(())
+    }
+}
+
+fn read_torrent(path: &Path) -> Result<(Vec<u8>, String)> {
+    let contents = std::fs::read(path)?;
+    let hash = bencode::infohash_hex(&contents)?;
+    Ok((contents, hash))
+}
+
+// The infohashes of every download loaded on `server`.
+fn server_hashes(server: &Server) -> Result<BTreeSet<String>> {
+    d::MultiBuilder::new(server, "main")
+        .dynamic()
+        .call("d.hash")
+        .invoke()?
+        .iter()
+        .map(|row| Ok(value_conversion::string(&row[0])?.to_owned()))
+        .collect()
+}